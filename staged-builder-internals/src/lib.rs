@@ -1,12 +1,14 @@
 use heck::{ToSnakeCase, ToUpperCamelCase};
-use proc_macro2::{Span, TokenStream};
-use quote::quote;
-use structmeta::{NameArgs, NameValue, StructMeta};
+use proc_macro2::{Delimiter, Group, Span, TokenStream, TokenTree};
+use quote::{quote, ToTokens};
+use structmeta::{Flag, NameArgs, NameValue, StructMeta};
+use syn::ext::IdentExt;
 use syn::parse::{Parse, ParseStream};
 use syn::spanned::Spanned;
 use syn::{
-    parse_macro_input, Attribute, Data, DeriveInput, Error, Expr, Field, Fields, FieldsNamed,
-    Ident, Path, Type, Visibility,
+    parse_macro_input, Attribute, Data, DataEnum, DeriveInput, Error, Expr, ExprClosure, Field,
+    Fields, GenericArgument, Generics, Ident, LitBool, LitInt, LitStr, Member, Path, PathArguments,
+    ReturnType, Token, Type, TypeParamBound, Visibility,
 };
 
 /// Creates a staged builder interface for structs.
@@ -16,53 +18,311 @@ use syn::{
 /// type named after it, with an additional "final" stage to set optional fields and construct the final value.
 ///
 /// By default, all fields are considered required and their setters will simply take their declared type by-value. This
-/// behavior can be customized with field options.
+/// behavior can be customized with field options. A field's doc comments are copied onto its setters.
+///
+/// A field's `#[deprecated]` attribute is copied onto all of the builder methods which set it, so callers are warned
+/// when they use them. Since the setter of a required field is the only way to advance past its stage, deprecating a
+/// required field effectively deprecates constructing the struct with the builder.
+///
+/// Since the builder's methods are defined in the same crate as the struct, a builder can be used to construct a
+/// `#[non_exhaustive]` struct from other crates.
+///
+/// Fields disabled by `#[cfg]` attributes are ignored, so conditionally compiled fields are supported as long as every
+/// configuration's set of fields is valid for the builder.
+///
+/// In addition to `build`, the final stage has a `try_build` method which always returns a `Result`. If the `validate`
+/// option is not set, its error type is [`Infallible`](core::convert::Infallible). This allows code to be written
+/// generically over builders whether or not they validate. A `build_into` method converts the built value into any
+/// type implementing `From` for it, like `build` followed by `.into()`, returning a `Result` if `build` does.
+///
+/// The final stage also has a `build_with` method which passes the constructed value to a closure for modification
+/// before returning it. The closure is called before any validation is performed.
+///
+/// The builder type is marked `#[must_use]`, so discarding the builder returned by a setter produces a warning.
+///
+/// The builder type has a `REQUIRED_FIELD_COUNT` associated constant containing the number of required fields which
+/// must be set before the value can be built. The names of the required fields are listed by the struct's
+/// `REQUIRED_FIELDS` constant if the `field_names` option is set.
+///
+/// Each optional field also has a `reset_foo` method on the final stage which sets it back to its default value.
+/// Collection fields are reset to an empty collection unless a custom default is provided. Optional fields also have a
+/// `map_foo` method which passes the field's current value to a closure and stores the value it returns. Collection
+/// fields are passed the whole collection. The method always takes the builder by value, even with the `by_ref` option,
+/// and isn't generated for fields using `default_from` or a fallible conversion.
+///
+/// Tuple structs are supported, with each element named by its position: the setter for the first element is named
+/// `field_0`, its stage `Field0Stage`, and so on.
+///
+/// Enums are supported by generating a separate builder for each variant with fields. For a variant `Foo::BarBaz`, a
+/// `bar_baz_builder` constructor function is added to the enum, and the builder types are placed in a `foo_bar_baz`
+/// submodule. Unit variants are skipped. The `mod`, `inline`, `new`, `update`, `impl_trait`, `default_struct`,
+/// `flatten`, `optional_field_docs`, `field_names`, `constructor`, `reexport`, and `from_single` options are not
+/// supported on enums.
+///
+/// Generic structs are supported, and the builder types will share the struct's generic parameters and bounds. Any
+/// bounds required by field options, such as `Hash + Eq` for the item type of a `set` field, must be declared on the
+/// struct itself. Every stage carries all of the struct's parameters, including lifetimes, even if the fields it stores
+/// don't reference them yet. For example, an `into` setter for a `Cow<'a, str>` field takes `impl Into<Cow<'a, str>>`,
+/// accepting both a borrowed `&'a str` and an owned `String` without copying the former.
 ///
 /// # Struct options
 ///
 /// Options can be applied at the struct level via the `#[builder(...)]` attribute as a comma-separated sequence:
 ///
 /// * `validate` - The final `build` method will return a `Result`, calling the type's `Validate` implementation before
-///     returning the constructed value.
+///   returning the constructed value.
+///   Alternatively, a closure can be provided inline, e.g.
+///   `validate = |s: &Foo| -> Result<(), &'static str> { ... }`. The closure must have a `Result<(), E>` return type
+///   annotation, and `E` will be used as the error type of `build`.
+///   A method name can also be provided, e.g. `validate = check`, in which case the type's inherent
+///   `fn check(&self) -> Result<(), E>` method is called instead of the `Validate` trait. A path to a free function
+///   taking `&Foo` can be used in the same way, e.g. `validate = checks::foo`. Either form requires the `error`
+///   option, and `E` must be convertible into the error type via `From`.
+/// * `normalize` - The final `build` method will return a `Result`, passing the constructed value through the type's
+///   `Normalize` implementation, which takes it by value and may transform it, for example by trimming strings or
+///   sorting collections. Normalization happens before any `distinct` checks and validation, which see the normalized
+///   value. If `validate` is also set without the `error` option, the `Validate::Error` type must implement
+///   `From<Normalize::Error>`. Otherwise, without `error`, the `Normalize::Error` type is returned by `build` and must
+///   implement `From<NotDistinct>` if `distinct` is set and `From<MissingFields>` if `unordered` is set.
+/// * `distinct` - Takes a list of field names which must all have distinct values, e.g. `distinct(from, to)`. The final
+///   `build` method will return a `Result`, returning a `NotDistinct` error if any of the fields are equal. If
+///   `validate` is also set, the `Validate::Error` type must implement `From<NotDistinct>`.
 /// * `update` - The completed stage of the builder will have setters for all fields, and a `From` impl will be created
-///     to allow an instance of the struct to be converted back into the builder type for further updates.
+///   to allow an instance of the struct to be converted back into the builder type for further updates, e.g.
+///   `Builder::from(value).foo(2).build()`. Any validation is performed again when the updated value is built.
 /// * `crate` - Indicates the path to the `staged_builder` crate root. Useful when reexporting the macro from another
-///     crate. Defaults to `::staged_builder`.
+///   crate. Defaults to `::staged_builder`.
+/// * `type_path` - The path used by the generated code to refer to the struct, e.g. `type_path = crate::foo::Bar`.
+///   Generic arguments are added automatically. Useful for generated code where `super::Bar` may not resolve to the
+///   struct. Defaults to `super::Bar`, or `Bar` if `inline` is set.
 /// * `mod` - The name of the submodule that will contain the generated builder types. Defaults to the struct's name
-///     converted to `snake_case`.
+///   converted to `snake_case`.
+/// * `mod_prefix` - A string prepended to the default name of the submodule. Ignored if `mod` is set.
+/// * `mod_suffix` - A string appended to the default name of the submodule. For example,
+///   `#[builder(mod_suffix = "_builder")]` on a struct named `Person` will produce a submodule named `person_builder`.
+///   Ignored if `mod` is set.
+/// * `setter_prefix` - A string prepended to the names of the setter methods named after fields. For example,
+///   `#[builder(setter_prefix = "with_")]` will produce a setter named `with_foo` for a field named `foo`. Collection
+///   setters like `push_foo` are unaffected.
+/// * `setter_suffix` - A string appended to the names of the setter methods named after fields.
+/// * `stage_suffix` - A string appended to the `PascalCase` names of fields to name their stage types. Defaults to
+///   `Stage`. For example, `#[builder(stage_suffix = "Step")]` will produce a stage named `FooStep` for a field named
+///   `foo`. Ignored for fields with the `stage` option.
 /// * `inline` - Causes the generated builder types to be defined in the same module as the struct, rather than a
-///     submodule.
+///   submodule.
+/// * `reexport` - Re-exports the builder and stage types from the submodule into the struct's module with the
+///   struct's visibility, so they can be named without the submodule's path. Since the types of multiple builders in
+///   the same module would conflict, the `builder` and `complete` options can be used to give them distinct names.
+/// * `constructor` - Sets the name of the associated function which returns a new builder. Defaults to `builder`.
 /// * `builder` - Sets the name of the generated builder type. Defaults to `Builder`.
+/// * `stage_vis` - Sets the visibility of the builder and stage types, e.g. `stage_vis = pub(crate)`. Defaults to the
+///   struct's visibility. The constructor function and builder submodule keep the struct's visibility.
 /// * `complete` - Sets the name of the generated complete stage type. Defaults to `Complete`.
+/// * `deref_view` - The complete stage of the builder will implement `Deref` to the complete stage type, which will
+///   expose the current values of all fields as public fields. This allows values to be read from the builder before
+///   `build` is called, for example `builder.field`.
+/// * `with` - The final stage of the builder will have a `with` method which calls a closure with a mutable reference
+///   to the complete stage type, whose fields are made public, allowing many fields to be set at once. This bypasses
+///   the field's setters and any conversions they perform. Fields are exposed in the form the builder stores them,
+///   so for example a field with a `lazy` default is wrapped in an `Option`.
+/// * `trace_defaults` - When the final value is built, a `tracing` debug event will be emitted for each optional field
+///   that was not explicitly set and fell back to its default value. Requires the `tracing` Cargo feature. Recent
+///   versions of `tracing` require a newer Rust version than the rest of this crate.
+/// * `unordered` - Rather than a sequence of stages, generates a single builder with setters for every field which can
+///   be called in any order. Since a missing required field can then only be detected at runtime, `build` will return
+///   a `MissingFields` error listing any required fields that were not set. If combined with `validate`, the
+///   `Validate::Error` type must implement `From<MissingFields>`. At most 64 required fields are supported.
+/// * `field_names` - Generates associated `FIELDS` and `REQUIRED_FIELDS` constants on the type listing the names of all
+///   fields and of the required fields respectively. Required fields are listed in the order the builder sets them.
+/// * `dynamic` - Additionally generates a `PartialBuilder` type which sets fields by name at runtime, intended for use
+///   by language bindings. Its `set_field_by_name` method takes the field's name and a value of the field's exact type,
+///   returning an `UnknownField` error if the type has no field with that name and type. Its `finish` method returns a
+///   `MissingFields` error if any required fields were not set. All field types must be `'static`, and the option
+///   cannot be combined with `validate` or `distinct`.
+/// * `inline_setters` - Controls which setter methods are marked `#[inline]`. Defaults to `all`, and can be set to
+///   `required_only` to only inline the setters of required fields, which can reduce code size for types with many
+///   optional fields.
+/// * `by_ref` - The setters of the final stage will take `&mut self` and return `&mut Self` rather than taking and
+///   returning the builder by value. The final stage will also have a `configure` method which calls a closure with a
+///   mutable reference to the builder and then returns it, allowing the setters to be used within a method chain.
+///   The setters of required fields still take the builder by value, since they advance it to a different stage.
+/// * `into` - Every field without an option determining its setters, like `custom` or `list`, will be treated as if it
+///   had the `into` option. Individual fields can opt out with `#[builder(into = false)]`.
+/// * `derive` - Adds derives to the builder type and each of its stages, e.g. `derive(Hash, serde::Serialize)`. The
+///   stages store the values of the fields which have been set under the fields' names, and the builder is a tuple
+///   struct wrapping the current stage. Derives which generate impls also implemented by the `debug`, `clone`, or `eq`
+///   options conflict with them.
+/// * `sealed` - Adds a private field to each stage type, so that stages can't be constructed outside of the
+///   generated code, even if they have no fields or the `deref_view` or `with` options make their fields public.
+/// * `const` - Makes the `builder` function, the setters of fields without a collection, `string`, `option`, or
+///   `flatten` option, and the final `build` method `const fn`s, so values can be built in const contexts. The
+///   generated code only compiles if everything it calls is const, so conversions like `into` and `custom` and
+///   defaults using [`Default`] generally can't be used, and `build` can't be fallible. Custom default expressions must
+///   be const. `const` can't be combined with `by_ref`, since `const fn`s taking `&mut self` require Rust 1.83.
+/// * `conditional_setters` - Each optional field without a collection, `string`, `option`, or `flatten` option will
+///   additionally have a `foo_if` setter taking a `bool` before the value, which only sets the field if it is `true`.
+///   This avoids breaking up a method chain to set fields based on runtime flags.
+/// * `default_all_optional_via = Default` - Every field with an `Option<_>` type will be treated as if it had the
+///   `default` option, defaulting to `None`. The setters of those fields are otherwise unaffected, and still take the
+///   `Option` itself.
+/// * `strip_option` - Every field with an `Option<_>` type and no other setter options will be treated as if it had
+///   the `option` option: it defaults to `None`, its setter takes the inner type and wraps it in `Some`, and an
+///   `unset_foo` method resets it to `None`. Only the last segment of the type's path is checked, so fully qualified
+///   types like `std::option::Option<T>` are detected, but type aliases of `Option` are not.
+/// * `error` - Sets the error type returned by the final `build` method, e.g. `error = MyError`. The errors produced by
+///   `validate`, `normalize`, `distinct`, `unordered`, and `try_into` fields are converted into it via `From`, so it
+///   must implement `From<NotDistinct>` if `distinct` is set and `From<MissingFields>` if `unordered` is set.
+///   `distinct` can only be combined with `unordered` if `validate` or `error` is also set.
+/// * `debug` - Implements `Debug` for the builder and each of its stages. A stage's implementation is only usable if
+///   the types of all fields it has stored so far implement `Debug`.
+/// * `clone` - Implements `Clone` for the builder and each of its stages, allowing a partially-configured builder to
+///   be reused. A stage's implementation is only usable if the types of all fields it has stored so far implement
+///   `Clone`.
+/// * `eq` - Implements `PartialEq` and `Eq` for the builder and each of its stages, comparing the values of all fields
+///   stored so far. A stage's implementations are only usable if the types of all fields it has stored so far
+///   implement the corresponding trait.
+/// * `boxed` - The final stage will additionally have a `build_boxed` method which returns the built value in a `Box`,
+///   wrapped in a `Result` if `build` returns one. Requires the `alloc` Cargo feature.
+/// * `rc` - The final stage will additionally have a `build_rc` method which returns the built value in an `Rc`,
+///   wrapped in a `Result` if `build` returns one. Requires the `alloc` Cargo feature.
+/// * `arc` - The final stage will additionally have a `build_arc` method which returns the built value in an `Arc`,
+///   wrapped in a `Result` if `build` returns one. Requires the `alloc` Cargo feature.
+/// * `default_struct` - Implements [`Default`] for the struct by building it with all fields set to their defaults.
+///   Every field must have a default, and `build` must not return a `Result`.
+/// * `flatten` - Allows the struct to be used by `flatten` fields of other builders. Every field must have a default
+///   and can't be skipped, and `build` must not return a `Result`.
+/// * `getters` - Each stage will have `get_foo` methods returning a reference to the value of each field which has
+///   already been set, allowing earlier values to be read while chaining.
+/// * `optional_field_docs` - Generates an associated `OPTIONAL_FIELDS` constant on the type listing the name and
+///   stringified default value expression of each optional field, for use by documentation tooling.
+/// * `new` - Rather than generating a staged builder, generates a `new` constructor function taking the value of the
+///   struct's only field. This is intended for newtypes, and can be used with both named and tuple structs. Field
+///   options like `into` and `custom` are respected. Since `new` can't fail, it can't be combined with `validate`,
+///   `normalize`, `distinct`, or `error`.
+/// * `from_single` - Implements [`From`] for the struct from any type convertible into its only field, e.g.
+///   `impl<T: Into<String>> From<T> for Name`, by passing the value to the builder. The struct must have exactly one
+///   non-skipped field, which must be required and use `into`, and `build` must not return a `Result`.
+/// * `impl_trait` - Implements the specified trait for the type, allowing generic code to construct builders for
+///   multiple types. The trait is expected to have the following shape:
+///
+///   ```ignore
+///   trait Configurable {
+///       /// The type returned by `builder`.
+///       type Builder;
+///
+///       /// The final stage of the builder.
+///       type Complete;
+///
+///       fn builder() -> Self::Builder;
+///   }
+///   ```
 ///
 /// # Field options
 ///
 /// Options can be applied to individual fields via the `#[builder(...)]` attribute as a comma-separated sequence:
 ///
 /// * `default` - Causes the field to be considered optional. The [`Default`] trait is normally used to generate the
-///     default field value. A custom default can be specified with `default = <expr>`, where `<expr>` is an expression.
+///   default field value. A custom default can be specified with `default = <expr>`, where `<expr>` is an expression.
+///   `Self` in the expression refers to the struct, so associated items can be used, e.g. `default = Self::PORT`.
+///   `default()` is equivalent to `default`. A `default` option takes precedence over the empty default of collection
+///   and `option` fields. The expression is normally evaluated when the builder is created; with
+///   `default = lazy(<expr>)` it is instead only evaluated by `build`, and only if the field was never set. This is
+///   useful for defaults which are expensive to compute. Lazy defaults can't be combined with `deref_view`.
+/// * `skip` - Omits the field from the builder entirely, so it has no setter methods and is always initialized to its
+///   default value. Must be combined with `default`.
+/// * `default_from` - Causes the field to be considered optional, defaulting to the value of another field, e.g.
+///   `#[builder(default_from = name)]`. The default is computed by `build` from a clone of the other field's final
+///   value, converted with [`From`], so the other field may be declared later in the struct. The other field can't
+///   itself use `default_from`.
 /// * `into` - Causes the setter method for the field to take `impl Into<FieldType>` rather than `FieldType` directly.
+///   An intermediate type can be specified with `into = <type>`, in which case the setter takes `impl Into<Type>` and
+///   converts the intermediate value into the field's type with [`From`]. For example, `#[builder(into = String)]`
+///   allows an `Arc<str>` field to be set from a `&str`. A callable expression can be specified with `then = <expr>`
+///   to perform the second conversion instead of `From`, e.g. `#[builder(into = String, then = Arc::from)]`. `then`
+///   requires `into = <type>`.
+///   `into = false` opts the field out of the struct-level `into` option.
+/// * `generic` - When combined with `into`, causes the setter method to take a named type parameter bounded by
+///   `Into<FieldType>` rather than `impl Into<FieldType>`, allowing callers to specify the type explicitly. For
+///   example, `#[builder(into, generic = S)]` results in a setter like `fn foo<S: Into<FieldType>>(self, foo: S)`.
+/// * `exact` - When combined with `into`, generates an additional `foo_exact` setter method which takes the field's
+///   type directly, avoiding the monomorphization of the generic setter for callers that already have a value of the
+///   field's type.
+/// * `try_into` - Causes the setter method for the field to take any `impl TryInto<FieldType>` whose error type can be
+///   converted into the struct-level `error` type, which must be set. Conversion errors are stored in the builder and
+///   returned by the final `build` method.
+/// * `boxed_fn` - For fields holding a trait object in a smart pointer like `Box<dyn Fn(i32) -> i32>`, causes the
+///   setter method to take `impl Fn(i32) -> i32 + 'static` and box it. The pointer type must have a `new` constructor.
 /// * `custom` - Causes the setter method to perform an arbitrary conversion for the field. The option expects a `type`
-///     which will be used as the argument type in the setter, and a `convert` callable expression which will be invoked
-///     by the setter. For example, the annotation `#[builder(into)]` on a field of type `T` is equivalent to the
-///     annotation `#[builder(custom(type = impl Into<T>, convert = Into::into))]`.
+///   which will be used as the argument type in the setter, and a `convert` callable expression which will be invoked
+///   by the setter. For example, the annotation `#[builder(into)]` on a field of type `T` is equivalent to the
+///   annotation `#[builder(custom(type = impl Into<T>, convert = Into::into))]`. If `fallible` is also specified, the
+///   `convert` expression returns a `Result` whose error type can be converted into the struct-level `error` type,
+///   which must be set. Conversion errors are stored in the builder and returned by the final `build` method, as with
+///   `try_into`. For example, `#[builder(custom(type = &str, convert = str::parse, fallible))]`. If `arg` is also
+///   specified, `convert` is instead an expression evaluated with the setter's argument bound to that name, e.g.
+///   `#[builder(custom(type = &str, arg = s, convert = s.to_owned()))]`.
 /// * `list` - Causes the field to be treated as a "list style" type. It will default to an empty collection, and three
-///     setter methods will be generated: `push_foo` to add a single value, `foo` to set the contents, and `extend_foo`
-///     to exend the collection with new values. The underlying type must have a `push` method, a [`FromIterator`]
-///     implementation, and an [`Extend`] implementation. The item type must be configured in the attribute:
-///     `#[builder(list(item(type = YourItemType)))]`.
+///   setter methods will be generated: `push_foo` to add a single value, `foo` to set the contents, and `extend_foo`
+///   to exend the collection with new values. The underlying type must have a `push` method, a [`FromIterator`]
+///   implementation, and an [`Extend`] implementation. The item type must be configured in the attribute:
+///   `#[builder(list(item(type = YourItemType)))]`.
+/// * `deque` - Causes the field to be treated as a "double-ended queue style" type like `VecDeque`. It behaves like
+///   `list`, but generates `push_back_foo` and `push_front_foo` methods to add a single value to either end of the
+///   collection instead of `push_foo`. `extend_foo` adds values to the back. The underlying type must have
+///   `push_back` and `push_front` methods. The item type is configured in the same way as the items of a `list`.
 /// * `set` - Causes the field to be treated as a "set style" type. It will default to an empty collection, and three
-///     setter methods will be generated: `insert_foo` to add a single value, `foo` to set the contents, and
-///     `extend_foo` to exend the collection with new values. The underlying type must have an `insert` method, a
-///     [`FromIterator`] implementation, and an [`Extend`] implementation. The item type must be configured in the
-///     attribute: `#[builder(set(item(type = YourItemType)))]`.
+///   setter methods will be generated: `insert_foo` to add a single value, `foo` to set the contents, and
+///   `extend_foo` to exend the collection with new values. The underlying type must have an `insert` method, a
+///   [`FromIterator`] implementation, and an [`Extend`] implementation. The item type must be configured in the
+///   attribute: `#[builder(set(item(type = YourItemType)))]`.
 /// * `map` - Causes the field to be treated as a "map style" type. It will default to an empty collection, and three
-///     setter methods will be generated: `insert_foo` to add a single entry, `foo` to set the contents, and
-///     `extend_foo` to exend the collection with new entries. The underlying type must have an `insert` method, a
-///     [`FromIterator`] implementation, and an [`Extend`] implementation. The key and value types must be configured in
-///     the attribute: `#[builder(map(key(type = YourKeyType), value(type = YourValueType)))]`.
+///   setter methods will be generated: `insert_foo` to add a single entry, `foo` to set the contents, and
+///   `extend_foo` to exend the collection with new entries. The underlying type must have an `insert` method, a
+///   [`FromIterator`] implementation, and an [`Extend`] implementation. The key and value types must be configured in
+///   the attribute: `#[builder(map(key(type = YourKeyType), value(type = YourValueType)))]`. Entries are passed to the
+///   collection in the order they are provided, so insertion-ordered maps like `IndexMap` retain their ordering.
+///
+///   The `list`, `deque`, `set`, and `map` options also accept a `type` naming the collection type, which is used to
+///   collect the values passed to `foo`, e.g. `#[builder(set(type = BTreeSet<u32>, item(type = u32)))]`. By default
+///   the collection type is inferred from the field's type.
+///
+///   They also accept a `capacity` expression, which causes the field to default to an empty collection created by
+///   the type's `with_capacity` method rather than [`Default`], e.g.
+///   `#[builder(list(item(type = u32), capacity = 128))]`. `Vec`, `VecDeque`, `HashSet`, and `HashMap` all support
+///   this, but `BTreeSet` and `BTreeMap` don't. `capacity` can't be combined with `default`.
+///
+///   The `list`, `deque`, and `set` options also accept a `whole_into` flag, which generates an additional `set_foo`
+///   method taking `impl Into<FieldType>`, allowing the entire collection to be replaced by a value such as an owned
+///   `Vec`.
+/// * `array` - Causes the setter method for a fixed-size array field to take each element as a separate argument,
+///   assembling them into the array. For example, `#[builder(array(len = 3, item(type = u8)))]` on a `[u8; 3]` field
+///   named `rgb` results in a setter like `fn rgb(self, rgb_0: u8, rgb_1: u8, rgb_2: u8)`. The item type is configured
+///   in the same way as the items of a `list`.
+/// * `string` - Causes the field to be treated as a string which is built up from formatted values. It will default to
+///   an empty string, and two setter methods will be generated: `push_foo` to append a value's `Display`
+///   representation, and `foo` to replace the contents with an `impl Into<FieldType>`. The underlying type must
+///   implement [`Default`] and [`core::fmt::Write`]. The type taken by `push_foo` defaults to `impl Display`, and can
+///   be overridden with `#[builder(string(push(type = YourType)))]`.
+/// * `option` - Causes the field to be treated as an `Option`. It will default to `None`, and two setter methods will
+///   be generated: `foo` to set the field to `Some` value, and `unset_foo` to reset it to `None`. The item type is
+///   configured in the same way as the items of a `list`: `#[builder(option(item(type = YourItemType)))]`.
+/// * `flatten` - Causes the field to be configured through its own type's staged builder. The field's type must use
+///   the struct-level `flatten` option. The field defaults to the value
+///   built with no fields set, and its setter takes a closure which is passed that builder, initialized from the
+///   field's current value, and returns it after configuring it, e.g. `.inner(|b| b.timeout(5))`.
+/// * `name` - Sets the name of the field's setter method, ignoring the `setter_prefix` and `setter_suffix` options.
+///   Collection setters like `push_foo` are named after it as well. The stage type is still named after the field.
+/// * `finalize` - Applies a transformation to the field's value when the final value is built. The option expects a
+///   callable expression which is passed the field's value and returns its final value. Unlike `custom`, which runs
+///   when the setter is called, `finalize` runs exactly once in `build`, regardless of how the value was set.
 /// * `stage`- Sets the name of the generated stage type. Defaults to the name of the field converted to `PascalCase`
-///     with `Stage` appended.
+///   with `Stage` (or the struct-level `stage_suffix`) appended. Consecutive required fields given the same stage name
+///   are set together by a single setter named after the stage in `snake_case`. For example, `x` and `y` fields both
+///   annotated with `#[builder(stage = Coords)]` are set with `.coords(x, y)`.
+///
+/// The `into`, `try_into`, `custom`, `boxed_fn`, `array`, `list`, `deque`, `set`, `map`, `string`, `option`, and
+/// `flatten` options each determine the setter methods generated for the field, so at most one of them can be applied
+/// to a field.
 ///
 /// # Collection type options
 ///
@@ -70,7 +330,13 @@ use syn::{
 ///
 /// * `type` - Indicates the type of the item in the collection. Required unless using `custom`.
 /// * `into` - Causes setter methods to take `impl<Into<ItemType>>` rather than `ItemType` directly.
+/// * `cloned` - Causes setter methods to take `impl Borrow<ItemType>` rather than `ItemType` directly, cloning the
+///   borrowed value. This allows collections to be extended from references, e.g. a slice of `ItemType`.
 /// * `custom` - Causes the setter methods to perform an arbitrary conversion for the field.
+/// * `list`/`set` - Indicates that the item is itself a collection, configured in the same way as the field options.
+///   Setter methods will take `impl IntoIterator<Item = NestedItemType>` and collect it into the item. For example,
+///   a `HashMap<String, Vec<i32>>` field could be configured with
+///   `#[builder(map(key(type = String), value(list(item(type = i32)))))]`.
 ///
 /// # Example expansion
 ///
@@ -186,6 +452,8 @@ pub fn staged_builder(
     let crate_ = overrides.crate_();
 
     let body = input.body;
+    // Derive inputs are configured before expansion, so fields disabled by `#[cfg]` attributes are never seen by the
+    // builder.
     quote! {
         #[derive(#crate_ ::__StagedBuilderInternalDerive)]
         #(#attrs)*
@@ -208,6 +476,18 @@ impl Parse for AttrInput {
     }
 }
 
+/// Derives a staged builder interface for structs.
+///
+/// This is equivalent to the [`macro@staged_builder`] attribute, and supports the same `#[builder(...)]` options.
+#[proc_macro_derive(StagedBuilder, attributes(builder))]
+pub fn derive_staged_builder(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+
+    expand(input)
+        .unwrap_or_else(|e| e.into_compile_error())
+        .into()
+}
+
 // Not public API.
 #[doc(hidden)]
 #[proc_macro_derive(__StagedBuilderInternalDerive, attributes(builder))]
@@ -220,31 +500,187 @@ pub fn __internal_derive_staged_builder(input: proc_macro::TokenStream) -> proc_
 }
 
 fn expand(input: DeriveInput) -> Result<TokenStream, Error> {
-    let struct_ = match &input.data {
-        Data::Struct(struct_) => struct_,
-        _ => {
+    let overrides = StructOverrides::new(&input.attrs)?;
+
+    match &input.data {
+        Data::Struct(struct_) => {
+            if overrides.new.value() {
+                return new_constructor(&input, &overrides, &struct_.fields);
+            }
+
+            if let Fields::Unit = &struct_.fields {
+                return Err(Error::new_spanned(
+                    &input,
+                    "staged builders cannot be derived on unit structs",
+                ));
+            }
+
+            expand_fields(&input, &overrides, &struct_.fields)
+        }
+        Data::Enum(enum_) => expand_enum(&input, &overrides, enum_),
+        Data::Union(_) => Err(Error::new_spanned(
+            &input,
+            "staged builders can only be derived on structs and enums",
+        )),
+    }
+}
+
+// Enums get a separate builder for each variant with fields. Each is expanded like a struct, with the input's data
+// narrowed to the single variant being built.
+fn expand_enum(
+    input: &DeriveInput,
+    overrides: &StructOverrides,
+    enum_: &DataEnum,
+) -> Result<TokenStream, Error> {
+    let unsupported = [
+        ("mod", overrides.mod_.is_some()),
+        ("inline", overrides.inline),
+        ("new", overrides.new.value()),
+        ("update", overrides.update),
+        ("impl_trait", overrides.impl_trait.is_some()),
+        ("default_struct", overrides.default_struct.value()),
+        ("flatten", overrides.flatten.value()),
+        ("optional_field_docs", overrides.optional_field_docs),
+        ("field_names", overrides.field_names),
+        ("constructor", overrides.constructor.is_some()),
+        ("reexport", overrides.reexport.value()),
+        ("from_single", overrides.from_single.value()),
+    ];
+    if let Some((option, _)) = unsupported.iter().find(|(_, set)| *set) {
+        return Err(Error::new_spanned(
+            &input.ident,
+            format!("`{option}` is not supported on enums"),
+        ));
+    }
+
+    let mut tokens = TokenStream::new();
+    for variant in &enum_.variants {
+        if let Fields::Unit = &variant.fields {
+            continue;
+        }
+
+        let mut variant_input = input.clone();
+        variant_input.data = Data::Enum(DataEnum {
+            enum_token: enum_.enum_token,
+            brace_token: enum_.brace_token,
+            variants: [variant.clone()].into_iter().collect(),
+        });
+        tokens.extend(expand_fields(&variant_input, overrides, &variant.fields)?);
+    }
+
+    Ok(tokens)
+}
+
+fn expand_fields(
+    input: &DeriveInput,
+    overrides: &StructOverrides,
+    fields: &Fields,
+) -> Result<TokenStream, Error> {
+    let (skipped, fields) = resolve_fields(input, overrides, fields)?
+        .into_iter()
+        .partition::<Vec<_>, _>(|f| f.skip);
+
+    if overrides.unordered.value() || overrides.dynamic.value() {
+        if let Some(field) = fields.iter().filter(|f| f.default.is_none()).nth(64) {
             return Err(Error::new_spanned(
-                &input,
-                "staged builders can only be derived on structs",
-            ))
+                field.field,
+                "`unordered` and `dynamic` support at most 64 required fields",
+            ));
         }
-    };
+    }
 
-    let fields = match &struct_.fields {
-        Fields::Named(fields) => fields,
-        _ => {
+    if !overrides.unordered.value() {
+        check_stage_groups(&fields)?;
+    }
+
+    if let Some(span) = overrides.default_struct.span {
+        if let Some(field) = fields.iter().find(|f| f.default.is_none()) {
             return Err(Error::new_spanned(
-                &input,
-                "staged builders cannot be derived on tuple or unit structs",
-            ))
+                field.field,
+                "`default_struct` requires all fields to have a default",
+            ));
         }
-    };
 
-    let overrides = StructOverrides::new(&input.attrs)?;
-    let fields = resolve_fields(&overrides, fields)?;
+        if build_error(input, overrides).is_some() {
+            return Err(Error::new(
+                span,
+                "`default_struct` cannot be combined with options that make `build` fallible",
+            ));
+        }
+    }
+
+    if let Some(span) = overrides.flatten.span {
+        if let Some(field) = fields.iter().find(|f| f.default.is_none()) {
+            return Err(Error::new_spanned(
+                field.field,
+                "`flatten` requires all fields to have a default",
+            ));
+        }
+
+        if let Some(field) = skipped.first() {
+            return Err(Error::new_spanned(
+                field.field,
+                "`flatten` cannot be combined with skipped fields",
+            ));
+        }
+
+        if build_error(input, overrides).is_some() {
+            return Err(Error::new(
+                span,
+                "`flatten` cannot be combined with options that make `build` fallible",
+            ));
+        }
+    }
+
+    if let Some(span) = overrides.from_single.span {
+        match &fields[..] {
+            [field] if field.default.is_none() => {
+                if field.into.is_none() {
+                    return Err(Error::new_spanned(
+                        field.field,
+                        "`from_single` requires the field to use `into`",
+                    ));
+                }
+            }
+            _ => {
+                return Err(Error::new(
+                    span,
+                    "`from_single` requires exactly one non-skipped field, which must be required",
+                ))
+            }
+        }
+
+        if build_error(input, overrides).is_some() {
+            return Err(Error::new(
+                span,
+                "`from_single` cannot be combined with options that make `build` fallible",
+            ));
+        }
+    }
+
+    if let Some(distinct) = &overrides.distinct {
+        for name in &distinct.args {
+            if !fields.iter().any(|f| f.name == *name) {
+                return Err(Error::new(name.span(), "unknown field"));
+            }
+        }
+    }
+
+    for source in fields.iter().filter_map(|f| f.default_from.as_ref()) {
+        match fields.iter().find(|f| f.name == *source) {
+            Some(field) if field.default_from.is_some() => {
+                return Err(Error::new(
+                    source.span(),
+                    "`default_from` cannot refer to a field which also uses `default_from`",
+                ))
+            }
+            Some(_) => {}
+            None => return Err(Error::new(source.span(), "unknown field")),
+        }
+    }
 
-    let builder_impl = builder_impl(&input, &overrides, &fields);
-    let module = module(&input, &overrides, &fields);
+    let builder_impl = builder_impl(input, overrides, &fields);
+    let module = module(input, overrides, &fields, &skipped);
 
     let tokens = quote! {
         #builder_impl
@@ -254,25 +690,105 @@ fn expand(input: DeriveInput) -> Result<TokenStream, Error> {
     Ok(tokens)
 }
 
+fn new_constructor(
+    input: &DeriveInput,
+    overrides: &StructOverrides,
+    fields: &Fields,
+) -> Result<TokenStream, Error> {
+    let mut iter = fields.iter();
+    // Point at the first extra field if there is one, since that's what needs to be removed.
+    let field = match (iter.next(), iter.next()) {
+        (Some(field), None) => field,
+        (Some(_), Some(extra)) => {
+            return Err(Error::new_spanned(
+                extra,
+                "`new` can only be used with structs with exactly one field",
+            ))
+        }
+        (None, _) => {
+            return Err(Error::new_spanned(
+                input,
+                "`new` can only be used with structs with exactly one field",
+            ))
+        }
+    };
+
+    let (name, member) = match &field.ident {
+        Some(ident) => (ident.clone(), Member::Named(ident.clone())),
+        None => (Ident::new("value", Span::call_site()), Member::from(0)),
+    };
+    let field = ResolvedField::new(input, overrides, field, name, member)?;
+
+    let (generics, params, assign) = match &field.mode {
+        FieldMode::Normal {
+            generics,
+            params,
+            assign,
+        } if field.default.is_none() => (generics, params, assign),
+        _ => {
+            return Err(Error::new_spanned(
+                field.field,
+                "`new` only supports required fields",
+            ))
+        }
+    };
+
+    let value = match &field.finalize {
+        Some(finalize) => call_convert(overrides, assign, finalize),
+        None => assign.clone(),
+    };
+
+    let member = &field.member;
+    let struct_name = &input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+    let vis = &input.vis;
+    let docs = format!("Creates a new [`{struct_name}`].");
+
+    Ok(quote! {
+        #[allow(clippy::redundant_field_names)]
+        impl #impl_generics #struct_name #ty_generics #where_clause {
+            #[doc = #docs]
+            #[inline]
+            #vis fn new #generics(#params) -> Self {
+                #struct_name {
+                    #member: #value,
+                }
+            }
+        }
+    })
+}
+
 fn module(
     input: &DeriveInput,
     overrides: &StructOverrides,
     fields: &[ResolvedField<'_>],
+    skipped: &[ResolvedField<'_>],
 ) -> TokenStream {
-    let builder = builder(input, overrides);
-    let default = default_impl(overrides, fields);
+    let builder = builder(input, overrides, fields);
+    let default = default_impl(input, overrides, fields);
+    let mut prev_stage = None;
     let stages = fields
         .iter()
         .enumerate()
-        .filter(|(_, f)| f.default.is_none())
-        .map(|(i, _)| stage(input, overrides, i, fields));
-    let final_stage = final_stage(input, overrides, fields);
+        .filter(|(_, f)| !overrides.unordered.value() && f.default.is_none())
+        .filter(|(_, f)| prev_stage.replace(&f.stage) != Some(&f.stage))
+        .map(|(i, _)| stage(input, overrides, i, fields))
+        .collect::<Vec<_>>();
+    let final_stage = final_stage(input, overrides, fields, skipped);
+    let partial_builder = if overrides.dynamic.value() {
+        partial_builder(input, overrides, fields, skipped)
+    } else {
+        quote!()
+    };
 
+    // Items are always emitted in this order, with stages in field declaration order, so that the expansion is stable
+    // for snapshot tests.
     let parts = quote! {
         #builder
         #default
         #(#stages)*
         #final_stage
+        #partial_builder
     };
 
     if overrides.inline {
@@ -282,7 +798,13 @@ fn module(
     let vis = &input.vis;
     let module_name = module_name(overrides, input);
 
-    let module_docs = format!("Builder types for [`{}`].", &input.ident);
+    let module_docs = format!("Builder types for [`{}`].", type_name(input));
+
+    let reexport = if overrides.reexport.value() {
+        quote!(#vis use #module_name::*;)
+    } else {
+        quote!()
+    };
 
     quote! {
         #[doc = #module_docs]
@@ -291,14 +813,35 @@ fn module(
 
             #parts
         }
+
+        #reexport
     }
 }
 
 fn module_name(overrides: &StructOverrides, input: &DeriveInput) -> Ident {
-    overrides
-        .mod_
-        .clone()
-        .unwrap_or_else(|| Ident::new(&input.ident.to_string().to_snake_case(), input.ident.span()))
+    overrides.mod_.clone().unwrap_or_else(|| {
+        let snake_name = match variant(input) {
+            Some(variant) => format!(
+                "{}_{}",
+                input.ident.unraw().to_string().to_snake_case(),
+                variant.unraw().to_string().to_snake_case(),
+            ),
+            None => input.ident.unraw().to_string().to_snake_case(),
+        };
+        let name = format!(
+            "{}{}{}",
+            overrides
+                .mod_prefix
+                .as_ref()
+                .map_or(String::new(), |p| p.value()),
+            snake_name,
+            overrides
+                .mod_suffix
+                .as_ref()
+                .map_or(String::new(), |s| s.value()),
+        );
+        Ident::new(&name, input.ident.span())
+    })
 }
 
 fn builder_impl(
@@ -316,138 +859,788 @@ fn builder_impl(
         let module_name = module_name(overrides, input);
         quote!(#module_name::)
     };
-    let stage_name = initial_stage(fields).unwrap_or_else(|| final_name(overrides));
+    let stage_name = initial_stage(overrides, fields).unwrap_or_else(|| final_name(overrides));
+    let final_name = final_name(overrides);
     let private = overrides.private();
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
 
-    quote! {
-        impl #name {
-            /// Returns a new builder.
-            #[inline]
-            #vis fn builder() -> #module_path #builder_name<#module_path #stage_name> {
-                #private::Default::default()
+    let (builder_fn, builder_docs) = match variant(input) {
+        Some(variant) => (
+            Ident::new(
+                &format!("{}_builder", variant.unraw().to_string().to_snake_case()),
+                variant.span(),
+            ),
+            format!("Returns a new builder for the `{variant}` variant."),
+        ),
+        None => (
+            overrides
+                .constructor
+                .clone()
+                .unwrap_or_else(|| Ident::new("builder", Span::call_site())),
+            "Returns a new builder.".to_string(),
+        ),
+    };
+
+    let const_ = overrides.const_();
+    let new = if overrides.const_.value() {
+        quote!(#module_path #builder_name::new())
+    } else {
+        quote!(#private::Default::default())
+    };
+
+    let trait_impl = match &overrides.impl_trait {
+        Some(trait_) => quote! {
+            impl #impl_generics #trait_ for #name #ty_generics #where_clause {
+                type Builder = #module_path #builder_name<#module_path #stage_name #ty_generics>;
+                type Complete = #module_path #builder_name<#module_path #final_name #ty_generics>;
+
+                #[inline]
+                fn builder() -> Self::Builder {
+                    #name::#builder_fn()
+                }
             }
+        },
+        None => quote!(),
+    };
+
+    let optional_fields = if overrides.optional_field_docs {
+        let optional_fields = fields.iter().filter(|f| f.default.is_some()).map(|f| {
+            let name = f.name.unraw().to_string();
+            let default = f.default_docs.as_deref().unwrap_or("Default::default()");
+            quote!((#name, #default))
+        });
+
+        quote! {
+            /// The names of the type's optional fields along with their default values.
+            #vis const OPTIONAL_FIELDS: &'static [(&'static str, &'static str)] = &[
+                #(#optional_fields,)*
+            ];
         }
-    }
-}
+    } else {
+        quote!()
+    };
 
-fn initial_stage(fields: &[ResolvedField<'_>]) -> Option<Ident> {
-    fields
-        .iter()
-        .find(|f| f.default.is_none())
-        .map(|f| f.stage.clone())
-}
+    let field_names = if overrides.field_names {
+        let names = fields.iter().map(|f| f.name.unraw().to_string());
+        let required_names = fields
+            .iter()
+            .filter(|f| f.default.is_none())
+            .map(|f| f.name.unraw().to_string());
 
-fn builder(input: &DeriveInput, overrides: &StructOverrides) -> TokenStream {
-    let link = if overrides.inline {
-        format!("[{}]", input.ident)
+        quote! {
+            /// The names of all of the type's fields.
+            #vis const FIELDS: &'static [&'static str] = &[#(#names),*];
+
+            /// The names of the type's required fields, in the order they are set by the builder.
+            #vis const REQUIRED_FIELDS: &'static [&'static str] = &[#(#required_names),*];
+        }
     } else {
-        format!("[{0}](super::{0})", input.ident)
+        quote!()
     };
 
-    let docs = format!("A builder for {link}");
+    let default_impl = if overrides.default_struct.value() {
+        quote! {
+            impl #impl_generics #private::Default for #name #ty_generics #where_clause {
+                #[inline]
+                fn default() -> Self {
+                    #name::#builder_fn().build()
+                }
+            }
+        }
+    } else {
+        quote!()
+    };
 
-    let vis = stage_vis(&input.vis, overrides);
-    let builder_name = builder_name(overrides);
+    let from_impl = match fields {
+        [field] if overrides.from_single.value() => {
+            from_single_impl(input, overrides, &builder_fn, field)
+        }
+        _ => quote!(),
+    };
 
     quote! {
-        #[doc = #docs]
-        #vis struct #builder_name<T>(T);
+        impl #impl_generics #name #ty_generics #where_clause {
+            #[doc = #builder_docs]
+            #[inline]
+            #[allow(clippy::type_complexity)]
+            #vis #const_ fn #builder_fn() -> #module_path #builder_name<#module_path #stage_name #ty_generics> {
+                #new
+            }
+
+            #field_names
+            #optional_fields
+        }
+
+        #trait_impl
+
+        #default_impl
+
+        #from_impl
     }
 }
 
-fn default_impl(overrides: &StructOverrides, fields: &[ResolvedField<'_>]) -> TokenStream {
-    let (stage, initializers) = match initial_stage(fields) {
-        Some(stage) => (stage, quote!()),
-        None => (final_name(overrides), default_field_initializers(fields)),
-    };
-    let builder_name = builder_name(overrides);
-
+fn from_single_impl(
+    input: &DeriveInput,
+    overrides: &StructOverrides,
+    builder_fn: &Ident,
+    field: &ResolvedField<'_>,
+) -> TokenStream {
+    let name = &input.ident;
     let private = overrides.private();
+    let setter = &field.setter;
+    let target = &field.into;
+
+    // The parameter can't shadow one of the struct's.
+    let mut param = "T".to_string();
+    while input.generics.type_params().any(|p| p.ident == param) {
+        param.push('_');
+    }
+    let param = Ident::new(&param, Span::call_site());
+
+    let mut generics = input.generics.clone();
+    generics.params.push(syn::parse2(quote!(#param)).unwrap());
+    generics
+        .make_where_clause()
+        .predicates
+        .push(syn::parse2(quote!(#param: #private::Into<#target>)).unwrap());
+    let (impl_generics, _, where_clause) = generics.split_for_impl();
+    let (_, ty_generics, _) = input.generics.split_for_impl();
 
     quote! {
-        impl #private::Default for #builder_name<#stage> {
+        impl #impl_generics #private::From<#param> for #name #ty_generics #where_clause {
             #[inline]
-            fn default() -> Self {
-                #builder_name(#stage {
-                    #initializers
-                })
+            fn from(v: #param) -> Self {
+                #name::#builder_fn().#setter(v).build()
             }
         }
     }
 }
 
-fn default_field_initializers(fields: &[ResolvedField<'_>]) -> TokenStream {
-    let fields = fields.iter().filter_map(|f| {
-        f.default.as_ref().map(|default| {
-            let name = f.field.ident.as_ref().unwrap();
-            quote!(#name: #default)
-        })
-    });
+fn initial_stage(overrides: &StructOverrides, fields: &[ResolvedField<'_>]) -> Option<Ident> {
+    if overrides.unordered.value() {
+        return None;
+    }
 
-    quote!(#(#fields,)*)
+    fields
+        .iter()
+        .find(|f| f.default.is_none())
+        .map(|f| f.stage.clone())
 }
 
-fn stage(
+fn builder(
     input: &DeriveInput,
     overrides: &StructOverrides,
-    idx: usize,
     fields: &[ResolvedField<'_>],
 ) -> TokenStream {
-    let vis = stage_vis(&input.vis, overrides);
-    let field = &fields[idx];
-    let name = field.field.ident.as_ref().unwrap();
+    let link = struct_link(input, overrides);
+    let docs = format!("A builder for {link}");
 
-    let (type_, assign) = match &field.mode {
-        FieldMode::Normal { type_, assign } => (type_, assign),
-        _ => unreachable!(),
-    };
+    let vis = stage_vis(input, overrides);
+    let builder_name = builder_name(overrides);
 
-    let stage_name = &field.stage;
+    let private = overrides.private();
 
-    let existing_fields = fields[..idx]
-        .iter()
-        .filter(|f| f.default.is_none())
-        .collect::<Vec<_>>();
+    let debug_impl = if overrides.debug {
+        let name = builder_name.to_string();
+        quote! {
+            impl<T> #private::fmt::Debug for #builder_name<T>
+            where
+                T: #private::fmt::Debug,
+            {
+                fn fmt(&self, f: &mut #private::fmt::Formatter<'_>) -> #private::fmt::Result {
+                    f.debug_tuple(#name).field(&self.0).finish()
+                }
+            }
+        }
+    } else {
+        quote!()
+    };
 
-    let existing_names = existing_fields
-        .iter()
-        .map(|f| f.field.ident.as_ref().unwrap())
-        .collect::<Vec<_>>();
-    let existing_types = existing_fields.iter().map(|f| &f.field.ty);
+    let clone_impl = if overrides.clone {
+        quote! {
+            impl<T> #private::Clone for #builder_name<T>
+            where
+                T: #private::Clone,
+            {
+                #[inline]
+                fn clone(&self) -> Self {
+                    #builder_name(#private::Clone::clone(&self.0))
+                }
+            }
+        }
+    } else {
+        quote!()
+    };
 
-    let (next_stage, optional_fields) = match fields[idx + 1..].iter().find(|f| f.default.is_none())
-    {
-        Some(field) => (field.stage.clone(), quote!()),
-        None => (final_name(overrides), default_field_initializers(fields)),
+    let eq_impl = if overrides.eq {
+        quote! {
+            impl<T> #private::PartialEq for #builder_name<T>
+            where
+                T: #private::PartialEq,
+            {
+                #[inline]
+                fn eq(&self, other: &Self) -> bool {
+                    #private::PartialEq::eq(&self.0, &other.0)
+                }
+            }
+
+            impl<T> #private::Eq for #builder_name<T> where T: #private::Eq {}
+        }
+    } else {
+        quote!()
     };
 
-    let builder_name = builder_name(overrides);
-    let struct_docs = format!("The `{name}` stage for [`{builder_name}`].");
-    let setter_docs = format!("Sets the `{name}` field.");
+    let required_fields = fields.iter().filter(|f| f.default.is_none()).count();
+    let derives = overrides.derives();
 
     quote! {
-        #[doc = #struct_docs]
-        #vis struct #stage_name {
-            #(#existing_names: #existing_types,)*
-        }
+        #[doc = #docs]
+        #[must_use = "builders do nothing unless built"]
+        #derives
+        #vis struct #builder_name<T>(T);
 
-        impl #builder_name<#stage_name> {
-            #[doc = #setter_docs]
-            #[inline]
-            pub fn #name(self, #name: #type_) -> #builder_name<#next_stage> {
-                #builder_name(#next_stage {
-                    #(#existing_names: self.0.#existing_names,)*
-                    #name: #assign,
-                    #optional_fields
-                })
-            }
+        impl<T> #builder_name<T> {
+            /// The number of required fields which must be set before the value can be built.
+            pub const REQUIRED_FIELD_COUNT: usize = #required_fields;
         }
+
+        #debug_impl
+        #clone_impl
+        #eq_impl
     }
 }
 
-fn stage_vis(vis: &Visibility, overrides: &StructOverrides) -> TokenStream {
-    if overrides.inline {
-        return quote!(#vis);
+// Implements the optional `Debug` and `Clone` traits for a stage. `marker` initializes the stage's marker field, if it
+// has one.
+fn stage_trait_impls(
+    input: &DeriveInput,
+    overrides: &StructOverrides,
+    stage_name: &Ident,
+    fields: &[&ResolvedField<'_>],
+    marker: &TokenStream,
+) -> TokenStream {
+    let private = overrides.private();
+    let names = fields.iter().map(|f| &f.name).collect::<Vec<_>>();
+
+    let debug_impl = if overrides.debug {
+        let generics =
+            stage_trait_generics(input, overrides, fields, &quote!(#private::fmt::Debug));
+        let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+        let name = stage_name.to_string();
+        let name_strs = names.iter().map(|n| n.unraw().to_string());
+
+        quote! {
+            impl #impl_generics #private::fmt::Debug for #stage_name #ty_generics #where_clause {
+                fn fmt(&self, f: &mut #private::fmt::Formatter<'_>) -> #private::fmt::Result {
+                    f.debug_struct(#name)
+                        #(.field(#name_strs, &self.#names))*
+                        .finish()
+                }
+            }
+        }
+    } else {
+        quote!()
+    };
+
+    let clone_impl = if overrides.clone {
+        let generics = stage_trait_generics(input, overrides, fields, &quote!(#private::Clone));
+        let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+
+        quote! {
+            impl #impl_generics #private::Clone for #stage_name #ty_generics #where_clause {
+                #[inline]
+                fn clone(&self) -> Self {
+                    #stage_name {
+                        #(#names: #private::Clone::clone(&self.#names),)*
+                        #marker
+                    }
+                }
+            }
+        }
+    } else {
+        quote!()
+    };
+
+    let eq_impl = if overrides.eq {
+        let partial_eq_generics =
+            stage_trait_generics(input, overrides, fields, &quote!(#private::PartialEq));
+        let (impl_generics, ty_generics, where_clause) = partial_eq_generics.split_for_impl();
+        let eq_generics = stage_trait_generics(input, overrides, fields, &quote!(#private::Eq));
+        let (eq_impl_generics, _, eq_where_clause) = eq_generics.split_for_impl();
+
+        quote! {
+            impl #impl_generics #private::PartialEq for #stage_name #ty_generics #where_clause {
+                #[inline]
+                fn eq(&self, other: &Self) -> bool {
+                    true #(&& #private::PartialEq::eq(&self.#names, &other.#names))*
+                }
+            }
+
+            impl #eq_impl_generics #private::Eq for #stage_name #ty_generics #eq_where_clause {}
+        }
+    } else {
+        quote!()
+    };
+
+    quote! {
+        #debug_impl
+        #clone_impl
+        #eq_impl
+    }
+}
+
+// Each stored field type is bounded with a higher-ranked bound so that non-generic types which don't implement the
+// trait simply leave the impl unusable rather than failing to compile.
+fn stage_trait_generics(
+    input: &DeriveInput,
+    overrides: &StructOverrides,
+    fields: &[&ResolvedField<'_>],
+    trait_: &TokenStream,
+) -> Generics {
+    let mut generics = input.generics.clone();
+    let where_clause = generics.make_where_clause();
+    for field in fields {
+        let ty = field.stored_type(overrides);
+        where_clause
+            .predicates
+            .push(syn::parse2(quote!(for<'__a> #ty: #trait_)).unwrap());
+    }
+    generics
+}
+
+fn default_impl(
+    input: &DeriveInput,
+    overrides: &StructOverrides,
+    fields: &[ResolvedField<'_>],
+) -> TokenStream {
+    let (stage, initializers) = match initial_stage(overrides, fields) {
+        Some(stage) => (stage, marker_initializer(input, overrides)),
+        None => (
+            final_name(overrides),
+            default_field_initializers(overrides, fields),
+        ),
+    };
+    let builder_name = builder_name(overrides);
+
+    let private = overrides.private();
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+
+    // `Default::default` can't be called in const contexts, so const builders are created by an inherent method.
+    if overrides.const_.value() {
+        return quote! {
+            impl #impl_generics #private::Default for #builder_name<#stage #ty_generics> #where_clause {
+                #[inline]
+                fn default() -> Self {
+                    Self::new()
+                }
+            }
+
+            impl #impl_generics #builder_name<#stage #ty_generics> #where_clause {
+                #[inline]
+                pub(crate) const fn new() -> Self {
+                    #builder_name(#stage {
+                        #initializers
+                    })
+                }
+            }
+        };
+    }
+
+    quote! {
+        impl #impl_generics #private::Default for #builder_name<#stage #ty_generics> #where_clause {
+            #[inline]
+            fn default() -> Self {
+                #builder_name(#stage {
+                    #initializers
+                })
+            }
+        }
+    }
+}
+
+// Skipped fields aren't stored in the builder, and are always initialized to their defaults.
+fn skipped_initializers(overrides: &StructOverrides, skipped: &[ResolvedField<'_>]) -> TokenStream {
+    let fields = skipped.iter().map(|f| {
+        let member = &f.member;
+        let value = f.default.as_ref().unwrap().clone();
+        let value = match &f.finalize {
+            Some(finalize) => call_convert(overrides, value, finalize),
+            None => value,
+        };
+        quote!(#member: #value)
+    });
+
+    quote!(#(#fields,)*)
+}
+
+fn default_field_initializers(
+    overrides: &StructOverrides,
+    fields: &[ResolvedField<'_>],
+) -> TokenStream {
+    let fields = fields.iter().filter_map(|f| {
+        let name = &f.name;
+        if f.optional_storage {
+            let private = overrides.private();
+            Some(quote!(#name: #private::Option::None))
+        } else {
+            let default = f.store(overrides, f.default.as_ref()?);
+            Some(quote!(#name: #default))
+        }
+    });
+    let sealed = sealed_field(overrides);
+
+    quote!(#(#fields,)* #sealed)
+}
+
+fn stage(
+    input: &DeriveInput,
+    overrides: &StructOverrides,
+    idx: usize,
+    fields: &[ResolvedField<'_>],
+) -> TokenStream {
+    let vis = stage_vis(input, overrides);
+    let field = &fields[idx];
+    let stage_name = &field.stage;
+
+    // Consecutive required fields sharing a stage are all set by the stage's setter.
+    let group = fields[idx..]
+        .iter()
+        .filter(|f| f.default.is_none())
+        .take_while(|f| f.stage == *stage_name)
+        .collect::<Vec<_>>();
+    let group_names = group.iter().map(|f| &f.name).collect::<Vec<_>>();
+    let (generics, params, assigns) = match &group[..] {
+        [field] => match &field.mode {
+            FieldMode::Normal {
+                generics,
+                params,
+                assign,
+            } => (generics.clone(), params.clone(), vec![assign]),
+            _ => unreachable!(),
+        },
+        group => {
+            let mut params = vec![];
+            let mut assigns = vec![];
+            for field in group {
+                match &field.mode {
+                    FieldMode::Normal {
+                        params: field_params,
+                        assign,
+                        ..
+                    } => {
+                        params.push(field_params);
+                        assigns.push(assign);
+                    }
+                    _ => unreachable!(),
+                }
+            }
+            (quote!(), quote!(#(#params),*), assigns)
+        }
+    };
+
+    let existing_fields = fields[..idx]
+        .iter()
+        .filter(|f| f.default.is_none())
+        .collect::<Vec<_>>();
+
+    let existing_names = existing_fields.iter().map(|f| &f.name).collect::<Vec<_>>();
+    let existing_types = existing_fields.iter().map(|f| f.stored_type(overrides));
+    let getters = getters(overrides, &existing_fields);
+    let trait_impls = stage_trait_impls(
+        input,
+        overrides,
+        stage_name,
+        &existing_fields,
+        &marker_initializer(input, overrides),
+    );
+
+    let (next_stage, optional_fields) = match fields[idx + 1..]
+        .iter()
+        .find(|f| f.default.is_none() && f.stage != *stage_name)
+    {
+        Some(field) => (field.stage.clone(), marker_initializer(input, overrides)),
+        None => (
+            final_name(overrides),
+            default_field_initializers(overrides, fields),
+        ),
+    };
+
+    let builder_name = builder_name(overrides);
+
+    let (setter_name, struct_docs, setter_docs) = match &group_names[..] {
+        [name] => (
+            field.setter.clone(),
+            format!("The `{}` stage for [`{builder_name}`].", name.unraw()),
+            field.setter_docs(&format!("Sets the `{}` field.", name.unraw())),
+        ),
+        names => {
+            let setter_name = stage_name.to_string().to_snake_case();
+            let list = names
+                .iter()
+                .map(|n| format!("`{}`", n.unraw()))
+                .collect::<Vec<_>>()
+                .join(", ");
+            let docs = format!("Sets the {list} fields.");
+            (
+                Ident::new(&setter_name, stage_name.span()),
+                format!("The {list} stage for [`{builder_name}`]."),
+                quote!(#[doc = #docs]),
+            )
+        }
+    };
+    let inline = overrides.setter_inline(true);
+    // A shared setter is deprecated if any of the fields it sets are.
+    let deprecated = group
+        .iter()
+        .map(|f| f.deprecated())
+        .find(|d| !d.is_empty())
+        .unwrap_or_default();
+    let inline = quote!(#deprecated #inline);
+    let const_ = overrides.const_();
+
+    let struct_generics = &input.generics;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+    let marker = marker_field(input, overrides);
+    let derives = overrides.derives();
+
+    let exact_setter = if field.exact {
+        let name = &field.name;
+        let exact_name = exact_setter_name(&field.setter);
+        let exact_docs = exact_setter_docs(name, &field.setter);
+        let type_ = &field.field.ty;
+        quote! {
+            #[doc = #exact_docs]
+            #inline
+            pub #const_ fn #exact_name(self, #name: #type_) -> #builder_name<#next_stage #ty_generics> {
+                #builder_name(#next_stage {
+                    #(#existing_names: self.0.#existing_names,)*
+                    #name: #name,
+                    #optional_fields
+                })
+            }
+        }
+    } else {
+        quote!()
+    };
+
+    quote! {
+        #[doc = #struct_docs]
+        #derives
+        // Stage structs and setters repeat the field types verbatim, so any complex type the user has allowed on their
+        // own struct would otherwise be linted again in the generated code.
+        #[allow(clippy::type_complexity)]
+        #vis struct #stage_name #struct_generics #where_clause {
+            #(#existing_names: #existing_types,)*
+            #marker
+        }
+
+        // Similarly, field initializers like `foo: foo` are linted when the field's name was passed through a
+        // `macro_rules!` macro.
+        #[allow(clippy::type_complexity, clippy::redundant_field_names)]
+        impl #impl_generics #builder_name<#stage_name #ty_generics> #where_clause {
+            #setter_docs
+            #inline
+            pub #const_ fn #setter_name #generics(self, #params) -> #builder_name<#next_stage #ty_generics> {
+                #builder_name(#next_stage {
+                    #(#existing_names: self.0.#existing_names,)*
+                    #(#group_names: #assigns,)*
+                    #optional_fields
+                })
+            }
+
+            #exact_setter
+
+            #getters
+        }
+
+        #trait_impls
+    }
+}
+
+// Required fields sharing a stage are set together by a single setter, so they must be declared consecutively and can't
+// use options which add to their individual setters.
+fn check_stage_groups(fields: &[ResolvedField<'_>]) -> Result<(), Error> {
+    let required = fields
+        .iter()
+        .filter(|f| f.default.is_none())
+        .collect::<Vec<_>>();
+
+    for (i, field) in required.iter().enumerate() {
+        if required.iter().filter(|f| f.stage == field.stage).count() == 1 {
+            continue;
+        }
+
+        if i > 0
+            && required[i - 1].stage != field.stage
+            && required[..i].iter().any(|f| f.stage == field.stage)
+        {
+            return Err(Error::new(
+                field.stage.span(),
+                "fields sharing a stage must be declared consecutively",
+            ));
+        }
+
+        let generic =
+            matches!(&field.mode, FieldMode::Normal { generics, .. } if !generics.is_empty());
+        if field.exact || generic {
+            return Err(Error::new(
+                field.stage.span(),
+                "fields sharing a stage cannot use `exact` or `generic`",
+            ));
+        }
+    }
+
+    Ok(())
+}
+
+fn getters(overrides: &StructOverrides, fields: &[&ResolvedField<'_>]) -> TokenStream {
+    if !overrides.getters {
+        return quote!();
+    }
+
+    let private = overrides.private();
+    let getters = fields.iter().map(|f| {
+        let name = &f.name;
+        let ty = &f.field.ty;
+        let getter = Ident::new(&format!("get_{}", name.unraw()), name.span());
+        let docs = format!("Returns the current value of the `{}` field.", name.unraw());
+
+        let (ret, body) = if f.optional_storage {
+            let stored = if f.fallible {
+                let error = &overrides.error;
+                quote!(#private::Result<#ty, #error>)
+            } else {
+                quote!(#ty)
+            };
+            (
+                quote!(#private::Option<&#stored>),
+                quote!(#private::Option::as_ref(&self.0.#name)),
+            )
+        } else if f.fallible {
+            let error = &overrides.error;
+            (
+                quote!(#private::Result<&#ty, &#error>),
+                quote!(#private::Result::as_ref(&self.0.#name)),
+            )
+        } else {
+            (quote!(&#ty), quote!(&self.0.#name))
+        };
+
+        quote! {
+            #[doc = #docs]
+            #[inline]
+            pub fn #getter(&self) -> #ret {
+                #body
+            }
+        }
+    });
+
+    quote!(#(#getters)*)
+}
+
+fn exact_setter_name(setter: &Ident) -> Ident {
+    Ident::new(&format!("{}_exact", setter.unraw()), setter.span())
+}
+
+fn exact_setter_docs(name: &Ident, setter: &Ident) -> String {
+    let name = name.unraw();
+    let setter = setter.unraw();
+    format!(
+        "Sets the `{name}` field.\n\nUnlike `{setter}`, this takes the field's type directly rather than performing \
+         a generic conversion."
+    )
+}
+
+fn struct_path(input: &DeriveInput, overrides: &StructOverrides) -> TokenStream {
+    let struct_name = &input.ident;
+    let (_, ty_generics, _) = input.generics.split_for_impl();
+    if let Some(type_path) = &overrides.type_path {
+        quote!(#type_path #ty_generics)
+    } else if overrides.inline {
+        quote!(#struct_name #ty_generics)
+    } else {
+        quote!(super::#struct_name #ty_generics)
+    }
+}
+
+// Struct expressions can't include generic arguments without a turbofish, so they rely on inference instead.
+fn struct_expr_path(input: &DeriveInput, overrides: &StructOverrides) -> TokenStream {
+    let struct_name = &input.ident;
+    let variant = variant(input).map(|v| quote!(::#v));
+    if let Some(type_path) = &overrides.type_path {
+        quote!(#type_path #variant)
+    } else if overrides.inline {
+        quote!(#struct_name #variant)
+    } else {
+        quote!(super::#struct_name #variant)
+    }
+}
+
+// The variant being built, if the input is an enum narrowed by `expand_enum`.
+fn variant(input: &DeriveInput) -> Option<&Ident> {
+    match &input.data {
+        Data::Enum(enum_) => enum_.variants.first().map(|v| &v.ident),
+        _ => None,
+    }
+}
+
+// The name of the struct or enum variant being built, relative to the type's module.
+fn type_name(input: &DeriveInput) -> String {
+    match variant(input) {
+        Some(variant) => format!("{}::{}", input.ident, variant),
+        None => input.ident.to_string(),
+    }
+}
+
+// Stages other than the final one may not hold fields referencing every generic parameter of the struct.
+fn marker_field(input: &DeriveInput, overrides: &StructOverrides) -> TokenStream {
+    let sealed = sealed_field(overrides);
+    if input.generics.params.is_empty() {
+        return sealed;
+    }
+
+    let private = overrides.private();
+    let struct_path = struct_path(input, overrides);
+    quote!(__marker: #private::PhantomData<fn() -> #struct_path>, #sealed)
+}
+
+fn marker_initializer(input: &DeriveInput, overrides: &StructOverrides) -> TokenStream {
+    let sealed = sealed_field(overrides);
+    if input.generics.params.is_empty() {
+        return sealed;
+    }
+
+    let private = overrides.private();
+    quote!(__marker: #private::PhantomData, #sealed)
+}
+
+// A private field prevents stages from being constructed outside of the generated code, even if they have no other
+// fields or their fields are public. The field's declaration and initializer are spelled the same.
+fn sealed_field(overrides: &StructOverrides) -> TokenStream {
+    if overrides.sealed {
+        quote!(__sealed: (),)
+    } else {
+        quote!()
+    }
+}
+
+fn struct_link(input: &DeriveInput, overrides: &StructOverrides) -> String {
+    let name = type_name(input);
+    if let Some(type_path) = &overrides.type_path {
+        let variant = variant(input).map_or_else(String::new, |v| format!("::{v}"));
+        format!("[`{name}`]({}{variant})", tokens_docs(type_path))
+    } else if overrides.inline {
+        format!("[`{name}`]")
+    } else {
+        format!("[`{name}`](super::{name})")
+    }
+}
+
+// The `stage_vis` option is interpreted relative to the struct's module, like the struct's own visibility.
+fn stage_vis(input: &DeriveInput, overrides: &StructOverrides) -> TokenStream {
+    let vis = overrides.stage_vis.as_ref().unwrap_or(&input.vis);
+    if overrides.inline {
+        return quote!(#vis);
     }
 
     match vis {
@@ -487,50 +1680,169 @@ fn final_stage(
     input: &DeriveInput,
     overrides: &StructOverrides,
     fields: &[ResolvedField<'_>],
+    skipped: &[ResolvedField<'_>],
 ) -> TokenStream {
-    let vis = stage_vis(&input.vis, overrides);
+    let vis = stage_vis(input, overrides);
     let builder_name = builder_name(overrides);
     let stage_name = final_name(overrides);
-    let struct_name = &input.ident;
-    let names = fields.iter().map(|f| f.field.ident.as_ref().unwrap());
-    let types = fields.iter().map(|f| &f.field.ty).collect::<Vec<_>>();
+    let names = fields.iter().map(|f| &f.name);
+    let types = fields.iter().map(|f| f.stored_type(overrides));
+    let trait_impls = stage_trait_impls(
+        input,
+        overrides,
+        &stage_name,
+        &fields.iter().collect::<Vec<_>>(),
+        &sealed_field(overrides),
+    );
+    let sealed = sealed_field(overrides);
+    let derives = overrides.derives();
 
-    let struct_docs = format!("The final stage for [`{struct_name}`](super::{struct_name}).");
+    let link = struct_link(input, overrides);
+    let struct_docs = format!("The final stage for {link}.");
+
+    let field_vis = if overrides.deref_view.value() || overrides.with {
+        quote!(pub)
+    } else {
+        quote!()
+    };
+    let field_docs = fields
+        .iter()
+        .map(|f| format!("The current value of the `{}` field.", f.name.unraw()));
 
     let setters = fields
         .iter()
-        .filter(|f| overrides.update || f.default.is_some())
+        .filter(|f| overrides.update || overrides.unordered.value() || f.default.is_some())
         .map(|f| final_stage_setter(overrides, f));
 
-    let build_docs =
-        format!("Consumes the builder, returning a [`{struct_name}`](super::{struct_name}).");
-
-    let build = if overrides.validate {
-        validated_build(input, overrides, fields)
+    let configure = if overrides.by_ref {
+        quote! {
+            /// Calls a closure with a mutable reference to the builder, returning the builder afterwards.
+            ///
+            /// This allows the builder's setters to be called imperatively, for example in a loop, without breaking
+            /// a method chain.
+            #[inline]
+            pub fn configure(mut self, f: impl FnOnce(&mut Self)) -> Self {
+                f(&mut self);
+                self
+            }
+        }
     } else {
-        unvalidated_build(input, overrides, fields)
+        quote!()
     };
 
+    let build = build(input, overrides, fields, skipped);
+
+    let try_build_docs = format!(
+        "Consumes the builder, returning a {link}.\n\nUnlike `build`, this always returns a `Result`."
+    );
+    let try_build = try_build(input, overrides);
+    let build_into = build_into(input, overrides);
+    let build_boxed = build_wrapped(input, overrides, overrides.boxed, "boxed", "Box", "a boxed");
+    let build_rc = build_wrapped(
+        input,
+        overrides,
+        overrides.rc,
+        "rc",
+        "Rc",
+        "a reference-counted",
+    );
+    let build_arc = build_wrapped(
+        input,
+        overrides,
+        overrides.arc,
+        "arc",
+        "Arc",
+        "an atomically reference-counted",
+    );
+    let getters = getters(overrides, &fields.iter().collect::<Vec<_>>());
+
     let update_from_impl = if overrides.update {
         update_from_impl(input, overrides, fields)
     } else {
         quote!()
     };
+    let flatten_impl = flatten_impl(input, overrides, fields);
+
+    let struct_generics = &input.generics;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+
+    let with = if overrides.with {
+        let (receiver, ret) = if overrides.by_ref {
+            (quote!(&mut self), quote!(&mut Self))
+        } else {
+            (quote!(mut self), quote!(Self))
+        };
+        quote! {
+            /// Calls a closure with a mutable reference to the complete stage, allowing its fields to be modified
+            /// directly.
+            ///
+            /// This bypasses the fields' setters, along with any conversions they perform.
+            #[inline]
+            pub fn with(#receiver, f: impl FnOnce(&mut #stage_name #ty_generics)) -> #ret {
+                f(&mut self.0);
+                self
+            }
+        }
+    } else {
+        quote!()
+    };
+
+    let deref_impl = if overrides.deref_view.value() {
+        let private = overrides.private();
+        quote! {
+            impl #impl_generics #private::Deref for #builder_name<#stage_name #ty_generics> #where_clause {
+                type Target = #stage_name #ty_generics;
+
+                #[inline]
+                fn deref(&self) -> &Self::Target {
+                    &self.0
+                }
+            }
+        }
+    } else {
+        quote!()
+    };
 
     quote! {
         #[doc = #struct_docs]
-        #vis struct #stage_name {
-            #(#names: #types,)*
+        #derives
+        #[allow(clippy::type_complexity)]
+        #vis struct #stage_name #struct_generics #where_clause {
+            #(
+                #[doc = #field_docs]
+                #field_vis #names: #types,
+            )*
+            #sealed
         }
 
-        impl #builder_name<#stage_name> {
+        #[allow(clippy::type_complexity)]
+        impl #impl_generics #builder_name<#stage_name #ty_generics> #where_clause {
             #(#setters)*
 
-            #[doc = #build_docs]
+            #configure
+
+            #with
+
             #build
+
+            #[doc = #try_build_docs]
+            #try_build
+
+            #build_into
+
+            #build_boxed
+
+            #build_rc
+
+            #build_arc
+
+            #getters
         }
 
         #update_from_impl
+        #flatten_impl
+        #deref_impl
+        #trait_impls
     }
 }
 
@@ -538,187 +1850,913 @@ fn final_stage_setter(
     struct_overrides: &StructOverrides,
     field: &ResolvedField<'_>,
 ) -> TokenStream {
-    let name = field.field.ident.as_ref().unwrap();
+    let name = &field.name;
+    let field_name = name.unraw();
+    let setter = &field.setter;
+    // Every method setting a deprecated field is deprecated along with it.
+    let deprecated = field.deprecated();
+    let inline = struct_overrides.setter_inline(field.default.is_none());
+    let inline = quote!(#deprecated #inline);
+    let const_ = struct_overrides.const_();
+    let (receiver, ret) = if struct_overrides.by_ref {
+        (quote!(&mut self), quote!(&mut Self))
+    } else {
+        (quote!(mut self), quote!(Self))
+    };
+
+    let setters = match &field.mode {
+        FieldMode::Normal {
+            generics,
+            params,
+            assign,
+        } => {
+            let docs = field.setter_docs(&format!("Sets the `{field_name}` field."));
+            let store = field.store_assign(struct_overrides, assign);
+
+            let exact_setter = if field.exact {
+                let exact_name = exact_setter_name(&field.setter);
+                let exact_docs = exact_setter_docs(name, &field.setter);
+                let type_ = &field.field.ty;
+                let store = field.store(struct_overrides, &quote!(#name));
+                quote! {
+                    #[doc = #exact_docs]
+                    #inline
+                    pub #const_ fn #exact_name(#receiver, #name: #type_) -> #ret {
+                        self.0.#name = #store;
+                        self
+                    }
+                }
+            } else {
+                quote!()
+            };
+
+            let conditional_setter = if struct_overrides.conditional_setters
+                && field.default.is_some()
+            {
+                let conditional_name =
+                    Ident::new(&format!("{}_if", field.setter.unraw()), field.setter.span());
+                let conditional_docs = format!(
+                    "Sets the `{field_name}` field if `condition` is `true`, and leaves it unchanged otherwise."
+                );
+                quote! {
+                    #[doc = #conditional_docs]
+                    #inline
+                    pub #const_ fn #conditional_name #generics(#receiver, condition: bool, #params) -> #ret {
+                        if condition {
+                            self.0.#name = #store;
+                        }
+                        self
+                    }
+                }
+            } else {
+                quote!()
+            };
 
-    match &field.mode {
-        FieldMode::Normal { type_, assign } => {
-            let docs = format!("Sets the `{name}` field.");
             quote! {
-                #[doc = #docs]
-                #[inline]
-                pub fn #name(mut self, #name: #type_) -> Self {
-                    self.0.#name = #assign;
+                #docs
+                #inline
+                pub #const_ fn #setter #generics(#receiver, #params) -> #ret {
+                    self.0.#name = #store;
                     self
                 }
+
+                #exact_setter
+
+                #conditional_setter
             }
         }
-        FieldMode::Seq { push, item } => {
+        FieldMode::Seq {
+            push,
+            item,
+            from_iter,
+            whole_into,
+        } => {
             let type_ = &item.type_;
             let convert = item.convert(struct_overrides, name);
             let convert_iter = item.convert_iter(struct_overrides, name);
 
-            let push_docs = format!("Adds a value to the `{name}` field.");
-            let push_method = Ident::new(&format!("{push}_{name}"), name.span());
+            let private = struct_overrides.private();
+            let field_mut = field.field_mut(struct_overrides);
+            let store_iter = field.store(struct_overrides, &quote!(#from_iter(#convert_iter)));
 
-            let docs = format!("Sets the `{name}` field.");
+            let push_methods = push.iter().map(|(push, location)| {
+                let push_docs = field.setter_docs(&format!(
+                    "Adds a value to {location}the `{field_name}` field."
+                ));
+                let push_method = field.prefixed_method(push);
+                let push = Ident::new(push, Span::call_site());
 
-            let extend_docs = format!("Adds values to the `{name}` field.");
-            let extend_method = Ident::new(&format!("extend_{name}"), name.span());
+                quote! {
+                    #push_docs
+                    #inline
+                    pub fn #push_method(#receiver, #name: #type_) -> #ret {
+                        (#field_mut).#push(#convert);
+                        self
+                    }
+                }
+            });
 
-            let private = struct_overrides.private();
+            let docs = field.setter_docs(&format!("Sets the `{field_name}` field."));
 
-            quote! {
-                #[doc = #push_docs]
-                #[inline]
-                pub fn #push_method(mut self, #name: #type_) -> Self {
-                    self.0.#name.#push(#convert);
-                    self
+            let extend_docs = format!("Adds values to the `{field_name}` field.");
+            let extend_method = field.prefixed_method("extend");
+
+            let whole_setter = if *whole_into {
+                let ty = &field.field.ty;
+                let store = field.store(struct_overrides, &quote!(#private::Into::into(#name)));
+                let set_docs = format!(
+                    "Sets the `{field_name}` field from any `impl Into<{}>`.",
+                    tokens_docs(ty)
+                );
+                let set_method = field.prefixed_method("set");
+
+                quote! {
+                    #[doc = #set_docs]
+                    #inline
+                    pub fn #set_method(#receiver, #name: impl #private::Into<#ty>) -> #ret {
+                        self.0.#name = #store;
+                        self
+                    }
                 }
+            } else {
+                quote!()
+            };
 
-                #[doc = #docs]
-                #[inline]
-                pub fn #name(
-                    mut self,
+            quote! {
+                #(#push_methods)*
+
+                #docs
+                #inline
+                pub fn #setter(
+                    #receiver,
                     #name: impl #private::IntoIterator<Item = #type_>,
-                ) -> Self
+                ) -> #ret
                 {
-                    self.0.#name = #private::FromIterator::from_iter(#convert_iter);
+                    self.0.#name = #store_iter;
                     self
                 }
 
                 #[doc = #extend_docs]
-                #[inline]
+                #inline
                 pub fn #extend_method(
-                    mut self,
+                    #receiver,
                     #name: impl #private::IntoIterator<Item = #type_>,
-                ) -> Self
+                ) -> #ret
                 {
-                    #private::Extend::extend(&mut self.0.#name, #convert_iter);
+                    #private::Extend::extend(#field_mut, #convert_iter);
+                    self
+                }
+
+                #whole_setter
+            }
+        }
+        FieldMode::String { push_type } => {
+            let private = struct_overrides.private();
+            let ty = &field.field.ty;
+            let field_mut = field.field_mut(struct_overrides);
+            let store = field.store(struct_overrides, &quote!(#private::Into::into(#name)));
+
+            let push_docs =
+                format!("Appends a value's `Display` representation to the `{field_name}` field.");
+            let push_method = field.prefixed_method("push");
+
+            let docs = field.setter_docs(&format!("Sets the `{field_name}` field."));
+
+            quote! {
+                #[doc = #push_docs]
+                #inline
+                pub fn #push_method(#receiver, #name: #push_type) -> #ret {
+                    #private::Result::expect(
+                        #private::Write::write_fmt(#field_mut, #private::format_args!("{}", #name)),
+                        "a Display implementation returned an error unexpectedly",
+                    );
+                    self
+                }
+
+                #docs
+                #inline
+                pub fn #setter(#receiver, #name: impl #private::Into<#ty>) -> #ret {
+                    self.0.#name = #store;
+                    self
+                }
+            }
+        }
+        FieldMode::Option { item } => {
+            let private = struct_overrides.private();
+            let type_ = &item.type_;
+            let convert = item.convert(struct_overrides, name);
+            let store = field.store(struct_overrides, &quote!(#private::Option::Some(#convert)));
+            let store_none = field.store(struct_overrides, &quote!(#private::Option::None));
+
+            let docs = field.setter_docs(&format!("Sets the `{field_name}` field to `Some`."));
+
+            let unset_docs = format!("Sets the `{field_name}` field to `None`.");
+            let unset_method = field.prefixed_method("unset");
+
+            quote! {
+                #docs
+                #inline
+                pub fn #setter(#receiver, #name: #type_) -> #ret {
+                    self.0.#name = #store;
+                    self
+                }
+
+                #[doc = #unset_docs]
+                #inline
+                pub fn #unset_method(#receiver) -> #ret {
+                    self.0.#name = #store_none;
+                    self
+                }
+            }
+        }
+        FieldMode::Flatten => {
+            let private = struct_overrides.private();
+            let ty = &field.field.ty;
+            let field_mut = field.field_mut(struct_overrides);
+
+            let docs = field.setter_docs(&format!(
+                "Configures the `{field_name}` field by passing its builder to a closure."
+            ));
+
+            quote! {
+                #docs
+                #inline
+                pub fn #setter(
+                    #receiver,
+                    #name: impl FnOnce(
+                        <#ty as #private::Flatten>::Builder,
+                    ) -> <#ty as #private::Flatten>::Builder,
+                ) -> #ret {
+                    #private::Flatten::update(#field_mut, #name);
                     self
                 }
             }
         }
-        FieldMode::Map { key, value } => {
+        FieldMode::Map {
+            key,
+            value,
+            from_iter,
+        } => {
             let key_name = Ident::new("key", Span::call_site());
             let key_type = &key.type_;
             let key_convert = key.convert(struct_overrides, &key_name);
 
-            let value_name = Ident::new("value", Span::call_site());
-            let value_type = &value.type_;
-            let value_convert = value.convert(struct_overrides, &value_name);
+            let value_name = Ident::new("value", Span::call_site());
+            let value_type = &value.type_;
+            let value_convert = value.convert(struct_overrides, &value_name);
+
+            let private = struct_overrides.private();
+
+            let iter_convert = if key.convert.is_some() || value.convert.is_some() {
+                quote! {
+                    #private::Iterator::map(
+                        #private::IntoIterator::into_iter(#name),
+                        |(#key_name, #value_name)| (#key_convert, #value_convert)
+                    )
+                }
+            } else {
+                quote!(#name)
+            };
+
+            let field_mut = field.field_mut(struct_overrides);
+            let store_iter = field.store(struct_overrides, &quote!(#from_iter(#iter_convert)));
+
+            let insert_docs =
+                field.setter_docs(&format!("Adds an entry to the `{field_name}` field."));
+            let insert_method = field.prefixed_method("insert");
+
+            let docs = field.setter_docs(&format!("Sets the `{field_name}` field."));
+
+            let extend_docs = format!("Adds entries to the `{field_name}` field.");
+            let extend_method = field.prefixed_method("extend");
+
+            quote! {
+                #insert_docs
+                #inline
+                pub fn #insert_method(#receiver, #key_name: #key_type, #value_name: #value_type) -> #ret {
+                    (#field_mut).insert(#key_convert, #value_convert);
+                    self
+                }
+
+                #docs
+                #inline
+                pub fn #setter(
+                    #receiver,
+                    #name: impl #private::IntoIterator<Item = (#key_type, #value_type)>,
+                ) -> #ret {
+                    self.0.#name = #store_iter;
+                    self
+                }
+
+                #[doc = #extend_docs]
+                #inline
+                pub fn #extend_method(
+                    #receiver,
+                    #name: impl #private::IntoIterator<Item = (#key_type, #value_type)>,
+                ) -> #ret
+                {
+                    #private::Extend::extend(#field_mut, #iter_convert);
+                    self
+                }
+            }
+        }
+    };
+
+    let reset = match &field.default {
+        Some(default) => {
+            let reset_docs = format!("Resets the `{field_name}` field to its default value.");
+            let reset_method = field.prefixed_method("reset");
+            let value = if field.optional_storage {
+                let private = struct_overrides.private();
+                quote!(#private::Option::None)
+            } else {
+                field.store(struct_overrides, default)
+            };
+
+            quote! {
+                #[doc = #reset_docs]
+                #inline
+                pub fn #reset_method(#receiver) -> #ret {
+                    self.0.#name = #value;
+                    self
+                }
+            }
+        }
+        None => quote!(),
+    };
+
+    // The current value of fields with a fallible conversion or a default from another field may not be known yet.
+    let map = match &field.default {
+        Some(default) if !field.fallible && field.default_from.is_none() => {
+            let ty = &field.field.ty;
+            let map_docs = format!(
+                "Transforms the current value of the `{field_name}` field, which is its default value if it \
+                 hasn't been set."
+            );
+            let map_method = field.prefixed_method("map");
+            let value = if field.optional_storage {
+                let private = struct_overrides.private();
+                quote!(#private::Option::unwrap_or_else(self.0.#name, || #default))
+            } else {
+                quote!(self.0.#name)
+            };
+            let f = hygienic("f");
+            let store = field.store_assign(struct_overrides, &quote!(#f(#value)));
+
+            quote! {
+                #[doc = #map_docs]
+                #inline
+                pub fn #map_method(mut self, #f: impl FnOnce(#ty) -> #ty) -> Self {
+                    self.0.#name = #store;
+                    self
+                }
+            }
+        }
+        _ => quote!(),
+    };
+
+    quote! {
+        #setters
+        #reset
+        #map
+    }
+}
+
+// An explicit collection type disambiguates the `FromIterator` impl when it can't be inferred from the field type.
+fn from_iter(overrides: &StructOverrides, collection: Option<&Type>) -> TokenStream {
+    let private = overrides.private();
+    match collection {
+        Some(collection) => quote!(<#collection as #private::FromIterator<_>>::from_iter),
+        None => quote!(#private::FromIterator::from_iter),
+    }
+}
+
+fn partial_builder(
+    input: &DeriveInput,
+    overrides: &StructOverrides,
+    fields: &[ResolvedField<'_>],
+    skipped: &[ResolvedField<'_>],
+) -> TokenStream {
+    let vis = stage_vis(input, overrides);
+    let crate_ = overrides.crate_();
+    let private = overrides.private();
+    let struct_path = struct_path(input, overrides);
+    let struct_expr_path = struct_expr_path(input, overrides);
+    let struct_generics = &input.generics;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+
+    let names = fields.iter().map(|f| &f.name).collect::<Vec<_>>();
+    let name_strs = names
+        .iter()
+        .map(|n| n.unraw().to_string())
+        .collect::<Vec<_>>();
+    let types = fields.iter().map(|f| &f.field.ty).collect::<Vec<_>>();
+
+    let required = required_fields(overrides, fields, &quote!(self));
+    let skipped = skipped_initializers(overrides, skipped);
+
+    let mut required_idx = 0;
+    let initializers = fields.iter().map(|f| {
+        let name = &f.name;
+        let member = &f.member;
+        let value = match &f.default {
+            Some(default) => quote!(#private::Option::unwrap_or_else(self.#name, || #default)),
+            None => {
+                let idx = syn::Index::from(required_idx);
+                required_idx += 1;
+                quote!(required.#idx)
+            }
+        };
+        let value = match &f.finalize {
+            Some(finalize) => call_convert(overrides, value, finalize),
+            None => value,
+        };
+        quote!(#member: #value)
+    });
+
+    let link = struct_link(input, overrides);
+    let struct_docs = format!("A builder for {link} which sets fields dynamically by name.");
+    let finish_docs = format!(
+        "Consumes the builder, returning a {link}.\n\nReturns an error if any required fields have not been set."
+    );
+
+    quote! {
+        #[doc = #struct_docs]
+        #[allow(clippy::type_complexity)]
+        #vis struct PartialBuilder #struct_generics #where_clause {
+            #(#names: #private::Option<#types>,)*
+        }
+
+        impl #impl_generics #private::Default for PartialBuilder #ty_generics #where_clause {
+            #[inline]
+            fn default() -> Self {
+                PartialBuilder {
+                    #(#names: #private::Option::None,)*
+                }
+            }
+        }
+
+        #[allow(clippy::type_complexity)]
+        impl #impl_generics PartialBuilder #ty_generics #where_clause {
+            /// Sets the value of the field with the specified name.
+            ///
+            /// Returns an error if the type has no field with that name and type.
+            pub fn set_field_by_name<V>(
+                &mut self,
+                name: &str,
+                value: V,
+            ) -> #private::Result<(), #crate_::UnknownField>
+            where
+                V: 'static,
+                #(#types: 'static,)*
+            {
+                let mut value = #private::Option::Some(value);
+                let value: &mut dyn #private::Any = &mut value;
+                match name {
+                    #(
+                        #name_strs => {
+                            if let #private::Option::Some(value) = value.downcast_mut::<#private::Option<#types>>() {
+                                self.#names = value.take();
+                                return #private::Result::Ok(());
+                            }
+                        }
+                    )*
+                    _ => {}
+                }
+
+                #private::Result::Err(#crate_::UnknownField)
+            }
+
+            #[doc = #finish_docs]
+            pub fn finish(self) -> #private::Result<#struct_path, #crate_::MissingFields> {
+                #required
+                #private::Result::Ok(#struct_expr_path {
+                    #(#initializers,)*
+                    #skipped
+                })
+            }
+        }
+    }
+}
+
+fn build_error(input: &DeriveInput, overrides: &StructOverrides) -> Option<TokenStream> {
+    let struct_path = struct_path(input, overrides);
+    let crate_ = overrides.crate_();
+
+    if let Some(error) = &overrides.error {
+        Some(quote!(#error))
+    } else if let Some(validate) = overrides.validate_closure() {
+        let error = closure_error_type(validate);
+        Some(quote!(#error))
+    } else if overrides.validate.is_some() {
+        Some(quote!(<#struct_path as #crate_::Validate>::Error))
+    } else if overrides.normalize {
+        Some(quote!(<#struct_path as #crate_::Normalize>::Error))
+    } else if overrides.distinct.is_some() {
+        Some(quote!(#crate_::NotDistinct))
+    } else if overrides.unordered.value() {
+        Some(quote!(#crate_::MissingFields))
+    } else {
+        None
+    }
+}
+
+// `build` delegates to `build_with` so that the field initializers are only expanded once.
+fn build(
+    input: &DeriveInput,
+    overrides: &StructOverrides,
+    fields: &[ResolvedField<'_>],
+    skipped: &[ResolvedField<'_>],
+) -> TokenStream {
+    let struct_path = struct_path(input, overrides);
+    let struct_expr_path = struct_expr_path(input, overrides);
+    let f = hygienic("f");
+    let value = hygienic("value");
+    let (bindings, initializers) = build_initializers(input, overrides, fields);
+    let skipped = skipped_initializers(overrides, skipped);
+    let required = if overrides.unordered.value() {
+        required_fields(overrides, fields, &quote!(self.0))
+    } else {
+        quote!()
+    };
+
+    let link = struct_link(input, overrides);
+    let build_docs = format!("Consumes the builder, returning a {link}.");
+    let build_with_docs = format!(
+        "Consumes the builder, returning a {link} after passing it to a closure for modification."
+    );
+
+    let error = match build_error(input, overrides) {
+        Some(error) => error,
+        // `build_with` takes a closure, so `build` has to construct the value itself to be const.
+        None if overrides.const_.value() => {
+            return quote! {
+                #[doc = #build_docs]
+                #[inline]
+                pub const fn build(self) -> #struct_path {
+                    #bindings
+                    #struct_expr_path {
+                        #initializers
+                        #skipped
+                    }
+                }
+
+                #[doc = #build_with_docs]
+                #[inline]
+                pub fn build_with(self, #f: impl FnOnce(&mut #struct_path)) -> #struct_path {
+                    let mut #value = self.build();
+                    #f(&mut #value);
+                    #value
+                }
+            }
+        }
+        None => {
+            return quote! {
+                #[doc = #build_docs]
+                #[inline]
+                pub fn build(self) -> #struct_path {
+                    self.build_with(|_| {})
+                }
+
+                #[doc = #build_with_docs]
+                #[inline]
+                pub fn build_with(self, #f: impl FnOnce(&mut #struct_path)) -> #struct_path {
+                    #bindings
+                    let mut #value = #struct_expr_path {
+                        #initializers
+                        #skipped
+                    };
+                    #f(&mut #value);
+                    #value
+                }
+            }
+        }
+    };
+
+    let crate_ = overrides.crate_();
+    let private = overrides.private();
+
+    let distinct = distinct_checks(overrides, fields);
+    let validate = if let Some(validate) = overrides.validate_closure() {
+        quote!(#private::call_hack(#validate, &#value)?;)
+    } else if let Some(path) = overrides.validate_path() {
+        // A bare name refers to a method of the type, and anything else to a free function.
+        match path.get_ident() {
+            Some(method) => quote!(<#struct_path>::#method(&#value)?;),
+            None => quote!(#path(&#value)?;),
+        }
+    } else if overrides.validate.is_some() {
+        quote!(#crate_::Validate::validate(&#value)?;)
+    } else {
+        quote!()
+    };
+    let normalize = if overrides.normalize {
+        quote!(let #value = #crate_::Normalize::normalize(#value)?;)
+    } else {
+        quote!()
+    };
+    let checked = if overrides.normalize {
+        "normalized and validated"
+    } else {
+        "validated"
+    };
+    let build_with_docs =
+        format!("{build_with_docs}\n\nThe closure is called before the value is {checked}.");
+
+    quote! {
+        #[doc = #build_docs]
+        #[inline]
+        pub fn build(self) -> #private::Result<#struct_path, #error> {
+            self.build_with(|_| {})
+        }
+
+        #[doc = #build_with_docs]
+        #[inline]
+        pub fn build_with(
+            self,
+            #f: impl FnOnce(&mut #struct_path),
+        ) -> #private::Result<#struct_path, #error> {
+            #required
+            #bindings
+            let mut #value = #struct_expr_path {
+                #initializers
+                #skipped
+            };
+            #f(&mut #value);
+            #normalize
+            #distinct
+            #validate
+            #private::Result::Ok(#value)
+        }
+    }
+}
+
+// Locals and parameters of generated methods which are in scope of user-provided expressions like defaults resolve at
+// the macro's definition site so they can't shadow names those expressions refer to.
+fn hygienic(name: &str) -> Ident {
+    Ident::new(name, Span::mixed_site())
+}
+
+// Extracts `E` from a closure's `Result<(), E>` return type annotation.
+fn closure_error_type(closure: &ExprClosure) -> Option<&Type> {
+    let ty = match &closure.output {
+        ReturnType::Type(_, ty) => ty,
+        ReturnType::Default => return None,
+    };
+    let segment = match &**ty {
+        Type::Path(path) if path.qself.is_none() => path.path.segments.last()?,
+        _ => return None,
+    };
+    if segment.ident != "Result" {
+        return None;
+    }
+    let args = match &segment.arguments {
+        PathArguments::AngleBracketed(args) if args.args.len() == 2 => &args.args,
+        _ => return None,
+    };
+    match &args[1] {
+        GenericArgument::Type(ty) => Some(ty),
+        _ => None,
+    }
+}
+
+// Unwraps the optional storage of each required field into a `required` tuple, returning a `MissingFields` error if any
+// are unset.
+fn required_fields(
+    overrides: &StructOverrides,
+    fields: &[ResolvedField<'_>],
+    receiver: &TokenStream,
+) -> TokenStream {
+    let required = fields
+        .iter()
+        .filter(|f| f.default.is_none())
+        .map(|f| &f.name)
+        .collect::<Vec<_>>();
+    if required.is_empty() {
+        return quote!();
+    }
+    let required_names = required.iter().map(|n| n.unraw().to_string());
+
+    let private = overrides.private();
+
+    quote! {
+        let required = match (#(#receiver.#required,)*) {
+            (#(#private::Option::Some(#required),)*) => (#(#required,)*),
+            (#(#required,)*) => {
+                return #private::Result::Err(#private::From::from(#private::missing_fields(
+                    &[#(#required_names),*],
+                    &[#(#required.is_none()),*],
+                )));
+            }
+        };
+    }
+}
+
+fn distinct_checks(overrides: &StructOverrides, fields: &[ResolvedField<'_>]) -> TokenStream {
+    let distinct = match &overrides.distinct {
+        Some(distinct) => &distinct.args,
+        None => return quote!(),
+    };
 
-            let private = struct_overrides.private();
+    let private = overrides.private();
+    let value = hygienic("value");
+    let mut checks = vec![];
+    for (i, a) in distinct.iter().enumerate() {
+        for b in &distinct[i + 1..] {
+            let (a_member, b_member) = match (
+                fields.iter().find(|f| f.name == *a),
+                fields.iter().find(|f| f.name == *b),
+            ) {
+                (Some(a), Some(b)) => (&a.member, &b.member),
+                // unknown fields are reported by expand
+                _ => continue,
+            };
+            let a_name = a.unraw().to_string();
+            let b_name = b.unraw().to_string();
 
-            let iter_convert = if key.convert.is_some() || value.convert.is_some() {
-                quote! {
-                    #private::Iterator::map(
-                        #private::IntoIterator::into_iter(#name),
-                        |(#key_name, #value_name)| (#key_convert, #value_convert)
-                    )
+            checks.push(quote! {
+                if #value.#a_member == #value.#b_member {
+                    return #private::Result::Err(#private::From::from(
+                        #private::not_distinct(#a_name, #b_name),
+                    ));
                 }
-            } else {
-                quote!(#name)
-            };
+            });
+        }
+    }
 
-            let insert_docs = format!("Adds an entry to the `{name}` field.");
-            let insert_method = Ident::new(&format!("insert_{name}"), name.span());
+    quote!(#(#checks)*)
+}
 
-            let docs = format!("Sets the `{name}` field.");
+fn build_initializers(
+    input: &DeriveInput,
+    overrides: &StructOverrides,
+    fields: &[ResolvedField<'_>],
+) -> (TokenStream, TokenStream) {
+    let sources = fields
+        .iter()
+        .filter_map(|f| f.default_from.as_ref())
+        .collect::<Vec<_>>();
 
-            let extend_docs = format!("Adds entries to the `{name}` field.");
-            let extend_method = Ident::new(&format!("extend_{name}"), name.span());
+    let mut required_idx = 0;
+    let mut bindings = vec![];
+    let mut deferred = vec![];
+    let mut initializers = vec![];
+    for f in fields {
+        let name = &f.name;
+        let member = &f.member;
 
-            quote! {
-                #[doc = #insert_docs]
-                #[inline]
-                pub fn #insert_method(mut self, #key_name: #key_type, #value_name: #value_type) -> Self {
-                    self.0.#name.insert(#key_convert, #value_convert);
-                    self
-                }
+        let value = match &f.default {
+            None if f.optional_storage => {
+                let idx = syn::Index::from(required_idx);
+                required_idx += 1;
+                quote!(required.#idx)
+            }
+            Some(default) if f.optional_storage => {
+                let private = overrides.private();
+                let trace = if overrides.trace_defaults.value() {
+                    let message = format!(
+                        "`{}` field of `{}` was not set; using its default value",
+                        name.unraw(),
+                        type_name(input),
+                    );
+                    quote!(#private::tracing::debug!(#message);)
+                } else {
+                    quote!()
+                };
 
-                #[doc = #docs]
-                #[inline]
-                pub fn #name(
-                    mut self,
-                    #name: impl #private::IntoIterator<Item = (#key_type, #value_type)>,
-                ) -> Self {
-                    self.0.#name = #private::FromIterator::from_iter(#iter_convert);
-                    self
-                }
+                let default = if f.fallible {
+                    quote!(#private::Result::Ok(#default))
+                } else {
+                    quote!(#default)
+                };
 
-                #[doc = #extend_docs]
-                #[inline]
-                pub fn #extend_method(
-                    mut self,
-                    #name: impl #private::IntoIterator<Item = (#key_type, #value_type)>,
-                ) -> Self
-                {
-                    #private::Extend::extend(&mut self.0.#name, #iter_convert);
-                    self
+                quote! {
+                    match self.0.#name {
+                        #private::Option::Some(value) => value,
+                        #private::Option::None => {
+                            #trace
+                            #default
+                        }
+                    }
                 }
             }
+            _ => quote!(self.0.#name),
+        };
+
+        let value = if f.fallible { quote!(#value?) } else { value };
+
+        let value = match &f.finalize {
+            Some(finalize) => call_convert(overrides, value, finalize),
+            None => value,
+        };
+
+        // Fields defaulting to another field's value borrow its local, so they're initialized before it's moved.
+        if f.default_from.is_some() {
+            deferred.push(quote!(#member: #value));
+        } else if sources.contains(&name) {
+            let local = default_from_local(name);
+            bindings.push(quote!(let #local = #value;));
+            initializers.push(quote!(#member: #local));
+        } else {
+            initializers.push(quote!(#member: #value));
         }
     }
+
+    (
+        quote!(#(#bindings)*),
+        quote!(#(#deferred,)* #(#initializers,)*),
+    )
 }
 
-fn validated_build(
+// Emits a `build_{suffix}` method returning the built value in a smart pointer.
+fn build_wrapped(
     input: &DeriveInput,
     overrides: &StructOverrides,
-    fields: &[ResolvedField<'_>],
+    enabled: bool,
+    suffix: &str,
+    wrapper: &str,
+    description: &str,
 ) -> TokenStream {
-    let struct_name = &input.ident;
-    let struct_path = if overrides.inline {
-        quote!(#struct_name)
-    } else {
-        quote!(super::#struct_name)
+    if !enabled {
+        return quote!();
+    }
+
+    let struct_path = struct_path(input, overrides);
+    let private = overrides.private();
+    let link = struct_link(input, overrides);
+    let docs = format!("Consumes the builder, returning {description} {link}.");
+    let method = Ident::new(&format!("build_{suffix}"), Span::call_site());
+    let wrapper = Ident::new(wrapper, Span::call_site());
+
+    let (ret, body) = match build_error(input, overrides) {
+        Some(error) => (
+            quote!(#private::Result<#private::#wrapper<#struct_path>, #error>),
+            quote!(#private::Result::map(self.build(), #private::#wrapper::new)),
+        ),
+        None => (
+            quote!(#private::#wrapper<#struct_path>),
+            quote!(#private::#wrapper::new(self.build())),
+        ),
     };
-    let names = fields
-        .iter()
-        .map(|f| f.field.ident.as_ref().unwrap())
-        .collect::<Vec<_>>();
 
-    let crate_ = overrides.crate_();
+    quote! {
+        #[doc = #docs]
+        #[inline]
+        pub fn #method(self) -> #ret {
+            #body
+        }
+    }
+}
+
+fn build_into(input: &DeriveInput, overrides: &StructOverrides) -> TokenStream {
+    let struct_path = struct_path(input, overrides);
     let private = overrides.private();
+    let link = struct_link(input, overrides);
+    let docs = format!("Consumes the builder, returning a value converted from a {link}.");
+
+    // The parameter can't shadow one of the struct's.
+    let mut param = "T".to_string();
+    while input.generics.type_params().any(|p| p.ident == param) {
+        param.push('_');
+    }
+    let param = Ident::new(&param, Span::call_site());
+
+    let (ret, body) = match build_error(input, overrides) {
+        Some(error) => (
+            quote!(#private::Result<#param, #error>),
+            quote!(#private::Result::map(self.build(), #private::From::from)),
+        ),
+        None => (quote!(#param), quote!(#private::From::from(self.build()))),
+    };
 
     quote! {
+        #[doc = #docs]
         #[inline]
-        pub fn build(
-            self,
-        ) -> #private::Result<
-            #struct_path,
-            <#struct_path as #crate_::Validate>::Error,
-        > {
-            let value = #struct_path {
-                #(#names: self.0.#names,)*
-            };
-            #crate_::Validate::validate(&value)?;
-            #private::Result::Ok(value)
+        pub fn build_into<#param>(self) -> #ret
+        where
+            #param: #private::From<#struct_path>,
+        {
+            #body
         }
     }
 }
 
-fn unvalidated_build(
-    input: &DeriveInput,
-    overrides: &StructOverrides,
-    fields: &[ResolvedField<'_>],
-) -> TokenStream {
-    let struct_name = &input.ident;
-    let struct_path = if overrides.inline {
-        quote!(#struct_name)
-    } else {
-        quote!(super::#struct_name)
+fn try_build(input: &DeriveInput, overrides: &StructOverrides) -> TokenStream {
+    let struct_path = struct_path(input, overrides);
+    let private = overrides.private();
+
+    let (error, body) = match build_error(input, overrides) {
+        Some(error) => (error, quote!(self.build())),
+        None => (
+            quote!(#private::Infallible),
+            quote!(#private::Result::Ok(self.build())),
+        ),
     };
-    let names = fields
-        .iter()
-        .map(|f| f.field.ident.as_ref().unwrap())
-        .collect::<Vec<_>>();
 
     quote! {
         #[inline]
-        pub fn build(self) -> #struct_path {
-            #struct_path {
-                #(#names: self.0.#names,)*
-            }
+        pub fn try_build(self) -> #private::Result<#struct_path, #error> {
+            #body
         }
     }
 }
@@ -729,38 +2767,104 @@ fn update_from_impl(
     fields: &[ResolvedField<'_>],
 ) -> TokenStream {
     let private = overrides.private();
-    let struct_name = &input.ident;
-    let struct_path = if overrides.inline {
-        quote!(#struct_name)
-    } else {
-        quote!(super::#struct_name)
-    };
+    let struct_path = struct_path(input, overrides);
 
     let builder = builder_name(overrides);
     let complete = final_name(overrides);
-    let fields = fields.iter().map(|f| f.field.ident.as_ref().unwrap());
+    let from_value = complete_from_value(overrides, fields);
+
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
 
     quote! {
-        impl #private::From<#struct_path> for #builder<#complete> {
+        impl #impl_generics #private::From<#struct_path> for #builder<#complete #ty_generics> #where_clause {
             #[inline]
             fn from(v: #struct_path) -> Self {
-                #builder(#complete {
-                    #(#fields: v.#fields,)*
-                })
+                #from_value
+            }
+        }
+    }
+}
+
+// Returns an expression creating the complete stage from the fields of an instance of the struct named `v`.
+fn complete_from_value(overrides: &StructOverrides, fields: &[ResolvedField<'_>]) -> TokenStream {
+    let builder = builder_name(overrides);
+    let complete = final_name(overrides);
+    let names = fields.iter().map(|f| &f.name);
+    let values = fields.iter().map(|f| {
+        let member = &f.member;
+        f.store(overrides, &quote!(v.#member))
+    });
+
+    let sealed = sealed_field(overrides);
+
+    quote! {
+        #builder(#complete {
+            #(#names: #values,)*
+            #sealed
+        })
+    }
+}
+
+// Structs whose builders start in the complete stage and build infallibly can opt into being flattened into other
+// builders.
+fn flatten_impl(
+    input: &DeriveInput,
+    overrides: &StructOverrides,
+    fields: &[ResolvedField<'_>],
+) -> TokenStream {
+    if !overrides.flatten.value() {
+        return quote!();
+    }
+
+    let private = overrides.private();
+    let struct_path = struct_path(input, overrides);
+    let builder = builder_name(overrides);
+    let complete = final_name(overrides);
+    let from_value = complete_from_value(overrides, fields);
+
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+
+    quote! {
+        impl #impl_generics #private::Flatten for #struct_path #where_clause {
+            type Builder = #builder<#complete #ty_generics>;
+
+            #[inline]
+            fn builder() -> Self::Builder {
+                #private::Default::default()
+            }
+
+            #[inline]
+            fn build(builder: Self::Builder) -> Self {
+                builder.build()
+            }
+
+            #[inline]
+            fn into_builder(self) -> Self::Builder {
+                let v = self;
+                #from_value
             }
         }
     }
 }
 
 fn resolve_fields<'a>(
+    input: &DeriveInput,
     overrides: &StructOverrides,
-    fields: &'a FieldsNamed,
+    fields: &'a Fields,
 ) -> Result<Vec<ResolvedField<'a>>, Error> {
     let mut resolved_fields = vec![];
     let mut error = None::<Error>;
 
-    for field in &fields.named {
-        match ResolvedField::new(overrides, field) {
+    for (i, field) in fields.iter().enumerate() {
+        // The elements of tuple structs are named by position, e.g. `field_0`.
+        let (name, member) = match &field.ident {
+            Some(ident) => (ident.clone(), Member::Named(ident.clone())),
+            None => (
+                Ident::new(&format!("field_{i}"), field.span()),
+                Member::from(i),
+            ),
+        };
+        match ResolvedField::new(input, overrides, field, name, member) {
             Ok(field) => resolved_fields.push(field),
             Err(e) => match &mut error {
                 Some(error) => error.combine(e),
@@ -777,26 +2881,363 @@ fn resolve_fields<'a>(
 
 #[derive(StructMeta, Default)]
 struct StructOverrides {
-    validate: bool,
+    validate: Option<NameValue<Option<Expr>>>,
     #[struct_meta(name = "crate")]
     crate_: Option<Path>,
     #[struct_meta(name = "mod")]
     mod_: Option<Ident>,
+    mod_prefix: Option<LitStr>,
+    mod_suffix: Option<LitStr>,
+    setter_prefix: Option<LitStr>,
+    setter_suffix: Option<LitStr>,
+    stage_suffix: Option<LitStr>,
     inline: bool,
     builder: Option<Ident>,
     complete: Option<Ident>,
     update: bool,
+    impl_trait: Option<Path>,
+    new: Flag,
+    distinct: Option<NameArgs<Vec<Ident>>>,
+    optional_field_docs: bool,
+    field_names: bool,
+    deref_view: Flag,
+    trace_defaults: Flag,
+    unordered: Flag,
+    dynamic: Flag,
+    inline_setters: Option<Ident>,
+    by_ref: bool,
+    default_all_optional_via: Option<Ident>,
+    error: Option<Type>,
+    debug: bool,
+    clone: bool,
+    boxed: bool,
+    rc: bool,
+    arc: bool,
+    getters: bool,
+    default_struct: Flag,
+    flatten: Flag,
+    constructor: Option<Ident>,
+    stage_vis: Option<Visibility>,
+    eq: bool,
+    reexport: Flag,
+    type_path: Option<Path>,
+    strip_option: bool,
+    conditional_setters: bool,
+    into: bool,
+    #[struct_meta(name = "const")]
+    const_: Flag,
+    sealed: bool,
+    derive: Option<NameArgs<Vec<Path>>>,
+    from_single: Flag,
+    with: bool,
+    normalize: bool,
 }
 
 impl StructOverrides {
+    const OPTIONS: &'static [&'static str] = &[
+        "validate",
+        "crate",
+        "mod",
+        "mod_prefix",
+        "mod_suffix",
+        "setter_prefix",
+        "setter_suffix",
+        "stage_suffix",
+        "inline",
+        "builder",
+        "complete",
+        "update",
+        "impl_trait",
+        "new",
+        "distinct",
+        "optional_field_docs",
+        "field_names",
+        "deref_view",
+        "trace_defaults",
+        "unordered",
+        "dynamic",
+        "inline_setters",
+        "by_ref",
+        "default_all_optional_via",
+        "error",
+        "debug",
+        "clone",
+        "boxed",
+        "rc",
+        "arc",
+        "getters",
+        "default_struct",
+        "flatten",
+        "constructor",
+        "stage_vis",
+        "eq",
+        "reexport",
+        "type_path",
+        "strip_option",
+        "conditional_setters",
+        "into",
+        "const",
+        "sealed",
+        "derive",
+        "from_single",
+        "with",
+        "normalize",
+    ];
+
     fn new(attrs: &[Attribute]) -> Result<Self, Error> {
-        attrs
+        let overrides = attrs
             .iter()
             .filter(|a| a.meta.path().is_ident("builder"))
-            .map(|a| a.parse_args())
+            .map(|a| {
+                a.parse_args::<StructOverrides>()
+                    .map_err(|e| unknown_option_error(a, Self::OPTIONS).unwrap_or(e))
+            })
             .next()
-            .transpose()
-            .map(|o| o.unwrap_or_default())
+            .transpose()?
+            .unwrap_or_default();
+
+        for affix in [
+            &overrides.mod_prefix,
+            &overrides.mod_suffix,
+            &overrides.setter_prefix,
+            &overrides.setter_suffix,
+            &overrides.stage_suffix,
+        ]
+        .into_iter()
+        .flatten()
+        {
+            if !affix
+                .value()
+                .chars()
+                .all(|c| c == '_' || c.is_alphanumeric())
+            {
+                return Err(Error::new(
+                    affix.span(),
+                    "name affixes may only contain alphanumeric characters and `_`",
+                ));
+            }
+        }
+
+        overrides.check_conflicts()?;
+
+        if let Some(NameValue {
+            value: Some(validate),
+            ..
+        }) = &overrides.validate
+        {
+            let valid = match validate {
+                Expr::Closure(closure) => closure_error_type(closure).is_some(),
+                Expr::Path(path) => path.qself.is_none(),
+                _ => false,
+            };
+            if !valid {
+                return Err(Error::new_spanned(
+                    validate,
+                    "expected a closure with a `Result<(), E>` return type annotation or a method name",
+                ));
+            }
+
+            if let (Expr::Path(_), None) = (validate, &overrides.error) {
+                return Err(Error::new_spanned(
+                    validate,
+                    "`validate` with a method name requires the struct-level `error` option",
+                ));
+            }
+        }
+
+        if let Some(via) = &overrides.default_all_optional_via {
+            if via != "Default" {
+                return Err(Error::new(via.span(), "expected `Default`"));
+            }
+        }
+
+        if let Some(inline_setters) = &overrides.inline_setters {
+            if inline_setters != "all" && inline_setters != "required_only" {
+                return Err(Error::new(
+                    inline_setters.span(),
+                    "expected `all` or `required_only`",
+                ));
+            }
+        }
+
+        for prefix in [&overrides.mod_prefix, &overrides.setter_prefix]
+            .into_iter()
+            .flatten()
+        {
+            if prefix.value().starts_with(|c: char| c.is_numeric()) {
+                return Err(Error::new(
+                    prefix.span(),
+                    "name prefixes may not start with a number",
+                ));
+            }
+        }
+
+        Ok(overrides)
+    }
+
+    fn check_conflicts(&self) -> Result<(), Error> {
+        const CONFLICTS: &[(&str, &str)] = &[
+            ("new", "unordered"),
+            ("new", "dynamic"),
+            ("new", "deref_view"),
+            ("new", "trace_defaults"),
+            ("new", "default_struct"),
+            ("new", "flatten"),
+            ("new", "from_single"),
+            ("unordered", "deref_view"),
+            ("trace_defaults", "deref_view"),
+        ];
+
+        let modes = [
+            ("new", &self.new),
+            ("unordered", &self.unordered),
+            ("dynamic", &self.dynamic),
+            ("deref_view", &self.deref_view),
+            ("trace_defaults", &self.trace_defaults),
+            ("default_struct", &self.default_struct),
+            ("flatten", &self.flatten),
+            ("from_single", &self.from_single),
+        ];
+        let span = |name: &str| {
+            modes
+                .iter()
+                .find(|(mode, _)| *mode == name)
+                .and_then(|(_, flag)| flag.span)
+        };
+
+        let mut error = None::<Error>;
+        let mut push_error = |e: Error| match &mut error {
+            Some(error) => error.combine(e),
+            None => error = Some(e),
+        };
+
+        for (a, b) in CONFLICTS {
+            if let (Some(_), Some(b_span)) = (span(a), span(b)) {
+                push_error(Error::new(
+                    b_span,
+                    format!("`{b}` cannot be combined with `{a}`"),
+                ));
+            }
+        }
+
+        if let (Some(const_), true) = (self.const_.span, self.by_ref) {
+            push_error(Error::new(
+                const_,
+                "`const` cannot be combined with `by_ref`",
+            ));
+        }
+
+        if let Some(dynamic) = self.dynamic.span {
+            if self.validate.is_some() || self.distinct.is_some() {
+                push_error(Error::new(
+                    dynamic,
+                    "`dynamic` cannot be combined with `validate` or `distinct`",
+                ));
+            }
+        }
+
+        if let Some(new) = self.new.span {
+            if self.validate.is_some()
+                || self.normalize
+                || self.distinct.is_some()
+                || self.error.is_some()
+            {
+                push_error(Error::new(
+                    new,
+                    "`new` cannot be combined with `validate`, `normalize`, `distinct`, or `error`",
+                ));
+            }
+        }
+
+        if let (Some(_), Some(distinct)) = (self.unordered.span, &self.distinct) {
+            if self.validate.is_none() && self.error.is_none() {
+                push_error(Error::new(
+                    distinct.name_span,
+                    "`distinct` can only be combined with `unordered` when `validate` or `error` is also set",
+                ));
+            }
+        }
+
+        if let Some(reexport) = self.reexport.span {
+            if self.inline {
+                push_error(Error::new(
+                    reexport,
+                    "`reexport` cannot be combined with `inline`",
+                ));
+            }
+        }
+
+        match error {
+            Some(error) => Err(error),
+            None => Ok(()),
+        }
+    }
+
+    fn validate_closure(&self) -> Option<&ExprClosure> {
+        match &self.validate {
+            Some(NameValue {
+                value: Some(Expr::Closure(closure)),
+                ..
+            }) => Some(closure),
+            _ => None,
+        }
+    }
+
+    fn validate_path(&self) -> Option<&Path> {
+        match &self.validate {
+            Some(NameValue {
+                value: Some(Expr::Path(path)),
+                ..
+            }) => Some(&path.path),
+            _ => None,
+        }
+    }
+
+    fn stage_name(&self, name: &Ident, span: Span) -> Ident {
+        let name = format!(
+            "{}{}",
+            name.unraw().to_string().to_upper_camel_case(),
+            self.stage_suffix
+                .as_ref()
+                .map_or_else(|| "Stage".to_string(), |s| s.value()),
+        );
+        Ident::new(&name, span)
+    }
+
+    fn setter_name(&self, name: &Ident) -> Ident {
+        if self.setter_prefix.is_none() && self.setter_suffix.is_none() {
+            return name.clone();
+        }
+
+        let name = format!(
+            "{}{}{}",
+            self.setter_prefix
+                .as_ref()
+                .map_or(String::new(), |p| p.value()),
+            name.unraw(),
+            self.setter_suffix
+                .as_ref()
+                .map_or(String::new(), |s| s.value()),
+        );
+        Ident::new(&name, Span::call_site())
+    }
+
+    fn derives(&self) -> TokenStream {
+        match &self.derive {
+            Some(derive) => {
+                let paths = &derive.args;
+                quote!(#[derive(#(#paths),*)])
+            }
+            None => quote!(),
+        }
+    }
+
+    fn const_(&self) -> TokenStream {
+        if self.const_.value() {
+            quote!(const)
+        } else {
+            quote!()
+        }
     }
 
     fn crate_(&self) -> TokenStream {
@@ -810,33 +3251,195 @@ impl StructOverrides {
         let crate_ = self.crate_();
         quote!(#crate_::__private)
     }
+
+    fn setter_inline(&self, required: bool) -> TokenStream {
+        match &self.inline_setters {
+            Some(inline_setters) if inline_setters == "required_only" && !required => quote!(),
+            _ => quote!(#[inline]),
+        }
+    }
 }
 
 struct ResolvedField<'a> {
     field: &'a Field,
+    name: Ident,
+    member: Member,
     default: Option<TokenStream>,
     stage: Ident,
     mode: FieldMode,
+    finalize: Option<Expr>,
+    default_docs: Option<String>,
+    optional_storage: bool,
+    exact: bool,
+    fallible: bool,
+    skip: bool,
+    setter: Ident,
+    rename: Option<Ident>,
+    conversion_docs: Option<String>,
+    default_from: Option<Ident>,
+    // The type accepted by the field's setter via `Into`, if it uses `into`.
+    into: Option<TokenStream>,
+}
+
+impl ResolvedField<'_> {
+    /// Returns the field's `deprecated` attribute, which is copied onto the methods setting the field.
+    ///
+    /// Only the first is returned since an item can't have multiple `deprecated` attributes.
+    fn deprecated(&self) -> TokenStream {
+        self.field
+            .attrs
+            .iter()
+            .find(|a| a.path().is_ident("deprecated"))
+            .map_or_else(TokenStream::new, |a| a.to_token_stream())
+    }
+
+    /// Returns the doc attributes for a setter of the field, including the field's own documentation and a description
+    /// of any conversion performed by the setter.
+    ///
+    /// The field's documentation comes first so that it forms the setter's summary line, and all of its `doc`
+    /// attributes, including `#[doc(hidden)]`, are forwarded as-is.
+    fn setter_docs(&self, summary: &str) -> TokenStream {
+        let field_docs = self
+            .field
+            .attrs
+            .iter()
+            .filter(|a| a.path().is_ident("doc"))
+            .collect::<Vec<_>>();
+        let field_docs = if field_docs.is_empty() {
+            quote!()
+        } else {
+            quote!(#(#field_docs)* #[doc = ""])
+        };
+
+        let conversion_docs = match &self.conversion_docs {
+            Some(docs) => quote!(#[doc = ""] #[doc = #docs]),
+            None => quote!(),
+        };
+
+        quote! {
+            #field_docs
+            #[doc = #summary]
+            #conversion_docs
+        }
+    }
+
+    /// Returns the name of a setter method like `push_foo` which prefixes the field's name with an action.
+    fn prefixed_method(&self, action: &str) -> Ident {
+        let name = self.rename.as_ref().unwrap_or(&self.name);
+        Ident::new(&format!("{action}_{}", name.unraw()), name.span())
+    }
+
+    /// Returns the type used to store the field in the builder.
+    fn stored_type(&self, overrides: &StructOverrides) -> TokenStream {
+        let private = overrides.private();
+        let ty = &self.field.ty;
+        let ty = if self.fallible {
+            let error = &overrides.error;
+            quote!(#private::Result<#ty, #error>)
+        } else {
+            quote!(#ty)
+        };
+        if self.optional_storage {
+            quote!(#private::Option<#ty>)
+        } else {
+            ty
+        }
+    }
+
+    /// Returns an expression converting a value of the field's type into its stored form.
+    fn store(&self, overrides: &StructOverrides, value: &TokenStream) -> TokenStream {
+        if self.fallible {
+            let private = overrides.private();
+            self.store_assign(overrides, &quote!(#private::Result::Ok(#value)))
+        } else {
+            self.store_assign(overrides, value)
+        }
+    }
+
+    /// Returns an expression converting the output of a setter's `assign` expression into the field's stored form.
+    fn store_assign(&self, overrides: &StructOverrides, assign: &TokenStream) -> TokenStream {
+        if self.optional_storage {
+            let private = overrides.private();
+            quote!(#private::Option::Some(#assign))
+        } else {
+            quote!(#assign)
+        }
+    }
+
+    // Collections default to empty, preallocating storage if a capacity is provided.
+    fn collection_default(
+        &mut self,
+        overrides: &StructOverrides,
+        capacity: Option<Expr>,
+    ) -> Result<(), Error> {
+        match (&self.default, capacity) {
+            (Some(_), Some(capacity)) => {
+                return Err(Error::new_spanned(
+                    capacity,
+                    "`capacity` cannot be combined with `default`",
+                ));
+            }
+            (Some(_), None) => {}
+            (None, Some(capacity)) => {
+                let ty = &self.field.ty;
+                let default = quote!(<#ty>::with_capacity(#capacity));
+                self.default_docs = Some(default.to_string());
+                self.default = Some(default);
+            }
+            (None, None) => {
+                let private = overrides.private();
+                self.default = Some(quote!(#private::Default::default()));
+            }
+        }
+
+        Ok(())
+    }
+
+    // Returns an expression evaluating to a mutable reference to the field's value in the final stage.
+    fn field_mut(&self, overrides: &StructOverrides) -> TokenStream {
+        let name = &self.name;
+        match &self.default {
+            Some(default) if self.optional_storage => {
+                let private = overrides.private();
+                quote!(#private::Option::get_or_insert_with(&mut self.0.#name, || #default))
+            }
+            _ => quote!(&mut self.0.#name),
+        }
+    }
 }
 
+#[allow(clippy::large_enum_variant)]
 enum FieldMode {
     Normal {
-        type_: TokenStream,
+        generics: TokenStream,
+        params: TokenStream,
         assign: TokenStream,
     },
     Seq {
-        push: TokenStream,
+        // Pairs of the collection's method adding an item and the location it adds it to.
+        push: Vec<(&'static str, &'static str)>,
         item: ParamConfig,
+        from_iter: TokenStream,
+        whole_into: bool,
     },
     Map {
         key: ParamConfig,
         value: ParamConfig,
+        from_iter: TokenStream,
+    },
+    String {
+        push_type: TokenStream,
+    },
+    Option {
+        item: ParamConfig,
     },
+    Flatten,
 }
 
 struct ParamConfig {
     type_: TokenStream,
-    convert: Option<Expr>,
+    convert: Option<Convert>,
+    docs: Option<String>,
 }
 
 impl ParamConfig {
@@ -844,13 +3447,57 @@ impl ParamConfig {
         struct_overrides: &StructOverrides,
         overrides: NameArgs<ParamOverrides>,
     ) -> Result<Self, Error> {
+        if let Some(seq) = overrides.args.list.or(overrides.args.set) {
+            if let Some(capacity) = &seq.args.capacity {
+                return Err(Error::new_spanned(
+                    capacity,
+                    "`capacity` is only supported by field-level collections",
+                ));
+            }
+            if seq.args.whole_into {
+                return Err(Error::new(
+                    seq.name_span,
+                    "`whole_into` is only supported by field-level collections",
+                ));
+            }
+            let item = required_arg(seq.args.item, seq.name_span, "item")?;
+            let item = ParamConfig::new(struct_overrides, item)?;
+            let private = struct_overrides.private();
+            let from_iter = from_iter(struct_overrides, seq.args.type_.as_ref());
+            let item_type = &item.type_;
+            let value = Ident::new("value", Span::call_site());
+            let convert_iter = item.convert_iter(struct_overrides, &value);
+
+            let docs = match &item.docs {
+                Some(item_docs) => {
+                    format!("any iterator of items accepting {item_docs}, which is collected")
+                }
+                None => "any iterator of items, which is collected".to_string(),
+            };
+
+            return Ok(ParamConfig {
+                type_: quote!(impl #private::IntoIterator<Item = #item_type>),
+                convert: Some(Convert::Callable(
+                    syn::parse2(quote!(|#value| #from_iter(#convert_iter))).unwrap(),
+                )),
+                docs: Some(docs),
+            });
+        }
+
         match overrides.args.custom {
             Some(custom) => {
-                let type_ = custom.args.type_;
-                let convert = custom.args.convert;
+                if let Some(span) = custom.args.fallible.span {
+                    return Err(Error::new(
+                        span,
+                        "`fallible` is only supported by field-level `custom` options",
+                    ));
+                }
+                let (type_, convert) = resolve_custom(custom)?;
+                let docs = custom_docs(&type_, &convert);
                 Ok(ParamConfig {
                     type_: quote!(#type_),
                     convert: Some(convert),
+                    docs: Some(docs),
                 })
             }
             None => {
@@ -858,36 +3505,70 @@ impl ParamConfig {
                     Error::new(overrides.name_span, "missing `type` configuration")
                 })?;
 
-                let (type_, convert) = if overrides.args.into {
+                if overrides.args.into && overrides.args.cloned {
+                    return Err(Error::new(
+                        overrides.name_span,
+                        "`cloned` cannot be combined with `into`",
+                    ));
+                }
+
+                let (type_, convert, docs) = if overrides.args.into {
                     let private = struct_overrides.private();
                     (
                         quote!(impl #private::Into<#type_>),
-                        Some(syn::parse2(quote!(#private::Into::into)).unwrap()),
+                        Some(Convert::Callable(
+                            syn::parse2(quote!(#private::Into::into)).unwrap(),
+                        )),
+                        Some(format!("any `impl Into<{}>`", tokens_docs(type_))),
+                    )
+                } else if overrides.args.cloned {
+                    let private = struct_overrides.private();
+                    let arg = Ident::new("item", Span::call_site());
+                    (
+                        quote!(impl #private::Borrow<#type_>),
+                        Some(Convert::Binding {
+                            expr: syn::parse2(quote! {
+                                <#type_ as #private::Clone>::clone(
+                                    #private::Borrow::<#type_>::borrow(&#arg),
+                                )
+                            })
+                            .unwrap(),
+                            arg,
+                        }),
+                        Some(format!(
+                            "any `impl Borrow<{}>`, which is cloned",
+                            tokens_docs(type_)
+                        )),
                     )
                 } else {
-                    (quote!(#type_), None)
+                    (quote!(#type_), None, None)
                 };
 
-                Ok(ParamConfig { type_, convert })
+                Ok(ParamConfig {
+                    type_,
+                    convert,
+                    docs,
+                })
             }
         }
     }
 
     fn convert(&self, struct_overrides: &StructOverrides, name: &Ident) -> TokenStream {
         match &self.convert {
-            Some(convert_fn) => call_convert(struct_overrides, name, convert_fn),
+            Some(convert) => convert.call(struct_overrides, name),
             None => quote!(#name),
         }
     }
 
     fn convert_iter(&self, struct_overrides: &StructOverrides, name: &Ident) -> TokenStream {
         match &self.convert {
-            Some(convert_fn) => {
+            Some(convert) => {
+                let convert = convert.call(struct_overrides, name);
                 let private = struct_overrides.private();
                 quote! {
                     #private::Iterator::map(
                         #private::IntoIterator::into_iter(#name),
-                        #convert_fn,
+                        |#name| #convert,
                     )
                 }
             }
@@ -896,143 +3577,864 @@ impl ParamConfig {
     }
 }
 
+// The conversion applied to a setter's argument by a `custom` option.
+enum Convert {
+    // A callable expression which is passed the argument.
+    Callable(Expr),
+    // An expression evaluated with the argument bound to a name.
+    Binding { arg: Ident, expr: Expr },
+}
+
+impl Convert {
+    fn call(&self, struct_overrides: &StructOverrides, value: impl ToTokens) -> TokenStream {
+        match self {
+            Convert::Callable(expr) => call_convert(struct_overrides, value, expr),
+            Convert::Binding { arg, expr } => quote!({
+                let #arg = #value;
+                #expr
+            }),
+        }
+    }
+
+    fn expr(&self) -> &Expr {
+        match self {
+            Convert::Callable(expr) | Convert::Binding { expr, .. } => expr,
+        }
+    }
+}
+
 // Directly-invoked closures don't infer properly:
 // https://internals.rust-lang.org/t/directly-invoked-closure-inference-weirdness/20235
-fn call_convert(struct_overrides: &StructOverrides, name: &Ident, expr: &Expr) -> TokenStream {
+fn call_convert(
+    struct_overrides: &StructOverrides,
+    value: impl ToTokens,
+    expr: &Expr,
+) -> TokenStream {
     match expr {
         Expr::Closure(closure) => {
             let private = struct_overrides.private();
-            quote!(#private::call_hack(#closure, #name))
+            quote!(#private::call_hack(#closure, #value))
         }
-        expr => quote!(#expr(#name)),
+        expr => quote!(#expr(#value)),
     }
 }
 
 impl<'a> ResolvedField<'a> {
     fn new(
+        input: &DeriveInput,
         struct_overrides: &StructOverrides,
         field: &'a Field,
+        name: Ident,
+        member: Member,
     ) -> Result<ResolvedField<'a>, Error> {
-        let name = field.ident.as_ref().unwrap();
         let ty = &field.ty;
 
-        let stage = format!(
-            "{}Stage",
-            field
-                .ident
-                .as_ref()
-                .unwrap()
-                .to_string()
-                .to_upper_camel_case()
-        );
-        let stage = Ident::new(&stage, field.span());
+        let stage = struct_overrides.stage_name(&name, field.span());
 
         let mut resolved = ResolvedField {
             field,
+            name: name.clone(),
+            member,
             default: None,
             stage,
             mode: FieldMode::Normal {
-                type_: quote!(#ty),
+                generics: quote!(),
+                params: quote!(#name: #ty),
                 assign: quote!(#name),
             },
+            finalize: None,
+            default_docs: None,
+            optional_storage: false,
+            exact: false,
+            fallible: false,
+            skip: false,
+            setter: struct_overrides.setter_name(&name),
+            rename: None,
+            conversion_docs: None,
+            default_from: None,
+            into: None,
         };
 
         let overrides = FieldOverrides::new(&field.attrs)?;
+        overrides.check_conflicts()?;
+        let has_mode = overrides.modes().iter().any(|(_, span)| span.is_some());
+
+        if let Some(source) = &overrides.default_from {
+            if overrides.default.is_some() || overrides.skip.value() {
+                return Err(Error::new(
+                    source.span(),
+                    "`default_from` cannot be combined with `default` or `skip`",
+                ));
+            }
+            if struct_overrides.dynamic.value() || struct_overrides.deref_view.value() {
+                return Err(Error::new(
+                    source.span(),
+                    "`default_from` cannot be combined with `dynamic` or `deref_view`",
+                ));
+            }
+
+            let private = struct_overrides.private();
+            let local = default_from_local(source);
+            resolved.default = Some(quote!(#private::From::from(#private::Clone::clone(&#local))));
+            resolved.default_docs = Some(source.to_string());
+            resolved.default_from = Some(source.clone());
+        }
 
+        let mut lazy = false;
         if let Some(default) = overrides.default {
             let default = match default.value {
-                Some(v) => quote!(#v),
+                Some(v) => {
+                    let v = match lazy_default(&v) {
+                        Some(inner) => {
+                            if struct_overrides.deref_view.value() {
+                                return Err(Error::new_spanned(
+                                    &v,
+                                    "`lazy` defaults cannot be combined with `deref_view`",
+                                ));
+                            }
+                            lazy = true;
+                            inner.clone()
+                        }
+                        None => v,
+                    };
+                    resolved.default_docs = Some(v.to_token_stream().to_string());
+                    let struct_path = struct_path(input, struct_overrides);
+                    replace_self(v.to_token_stream(), &quote!(<#struct_path>))
+                }
                 None => {
                     let private = struct_overrides.private();
                     quote!(#private::Default::default())
                 }
             };
             resolved.default = Some(default)
+        } else if struct_overrides.default_all_optional_via.is_some() && is_option(ty) {
+            let private = struct_overrides.private();
+            resolved.default = Some(quote!(#private::Default::default()));
+        }
+
+        if let Some(span) = overrides.skip.span {
+            if resolved.default.is_none() {
+                return Err(Error::new(span, "`skip` requires `default`"));
+            }
+            resolved.skip = true;
         }
 
-        if overrides.into {
+        // A field without any options determining its setters inherits the struct-level `into` option.
+        let (into, into_ty) = match overrides.into.as_ref().map(|into| &into.value) {
+            Some(Some(IntoValue::Type(into_ty))) => (true, Some(into_ty)),
+            Some(Some(IntoValue::Enabled(enabled))) => (enabled.value, None),
+            Some(None) => (true, None),
+            None => (
+                struct_overrides.into
+                    && !has_mode
+                    && !(struct_overrides.strip_option && is_option(ty)),
+                None,
+            ),
+        };
+
+        if let Some(span) = overrides.exact.span {
+            if !into {
+                return Err(Error::new(span, "`exact` requires `into`"));
+            }
+            resolved.exact = true;
+        }
+
+        if let Some(generic) = &overrides.generic {
+            if !into {
+                return Err(Error::new(generic.span(), "`generic` requires `into`"));
+            }
+
+            if input.generics.type_params().any(|p| p.ident == *generic) {
+                return Err(Error::new(
+                    generic.span(),
+                    "`generic` conflicts with a type parameter of the struct",
+                ));
+            }
+        }
+
+        if let (None, Some(then)) = (into_ty, &overrides.then) {
+            return Err(Error::new_spanned(then, "`then` requires `into = ...`"));
+        }
+
+        if into {
+            let private = struct_overrides.private();
+            let target = match into_ty {
+                Some(into_ty) => quote!(#into_ty),
+                None => quote!(#ty),
+            };
+            let into = quote!(#private::Into::<#target>::into(#name));
+            let into_docs = format!("Accepts any `impl Into<{}>`", tokens_docs(&target));
+            let (assign, docs) = match (into_ty, &overrides.then) {
+                (_, Some(then)) => (
+                    call_convert(struct_overrides, into, then),
+                    format!(
+                        "{into_docs}, which is then passed to `{}`.",
+                        tokens_docs(then)
+                    ),
+                ),
+                (Some(_), None) => (
+                    quote!(#private::From::from(#into)),
+                    format!("{into_docs}, which is then converted with `From`."),
+                ),
+                (None, None) => (into, format!("{into_docs}.")),
+            };
+            resolved.conversion_docs = Some(docs);
+            resolved.into = Some(target.clone());
+            resolved.mode = match &overrides.generic {
+                Some(generic) => FieldMode::Normal {
+                    generics: quote!(<#generic: #private::Into<#target>>),
+                    params: quote!(#name: #generic),
+                    assign,
+                },
+                None => FieldMode::Normal {
+                    generics: quote!(),
+                    params: quote!(#name: impl #private::Into<#target>),
+                    assign,
+                },
+            }
+        } else if let Some(span) = overrides.try_into.span {
+            let error = fallible_error(struct_overrides, span, "try_into")?;
             let private = struct_overrides.private();
+            resolved.fallible = true;
+            resolved.conversion_docs = Some(format!(
+                "Accepts any `impl TryInto<{}>` whose error type can be converted into `{}`. Conversion errors are \
+                 returned by `build`.",
+                tokens_docs(ty),
+                tokens_docs(error),
+            ));
             resolved.mode = FieldMode::Normal {
-                type_: quote!(impl #private::Into<#ty>),
-                assign: quote!(#private::Into::into(#name)),
+                generics: quote!(),
+                params: quote!(#name: impl #private::TryIntoField<#ty, #error>),
+                assign: quote!(#private::TryIntoField::try_into_field(#name)),
             }
         } else if let Some(custom) = overrides.custom {
-            let type_ = custom.args.type_;
-            let convert = custom.args.convert;
+            let fallible = custom.args.fallible.span;
+            let (type_, convert) = resolve_custom(custom)?;
+            let docs = custom_docs(&type_, &convert);
+            let assign = convert.call(struct_overrides, &name);
+            let assign = match fallible {
+                Some(span) => {
+                    fallible_error(struct_overrides, span, "fallible")?;
+                    let private = struct_overrides.private();
+                    resolved.fallible = true;
+                    resolved.conversion_docs = Some(format!(
+                        "Accepts {docs}. Conversion errors are returned by `build`."
+                    ));
+                    quote!(#private::Result::map_err(#assign, #private::From::from))
+                }
+                None => {
+                    resolved.conversion_docs = Some(format!("Accepts {docs}."));
+                    assign
+                }
+            };
             resolved.mode = FieldMode::Normal {
-                type_: quote!(#type_),
-                assign: call_convert(struct_overrides, name, &convert),
+                generics: quote!(),
+                params: quote!(#name: #type_),
+                assign,
+            }
+        } else if overrides.boxed_fn.value() {
+            let (mode, docs) = boxed_fn_mode(ty, &name)?;
+            resolved.mode = mode;
+            resolved.conversion_docs = Some(docs);
+        } else if let Some(array) = overrides.array {
+            let len = array.args.len.base10_parse::<usize>()?;
+            let item = required_arg(array.args.item, array.name_span, "item")?;
+            let item = ParamConfig::new(struct_overrides, item)?;
+            let item_type = &item.type_;
+
+            let params = (0..len)
+                .map(|i| Ident::new(&format!("{name}_{i}"), name.span()))
+                .collect::<Vec<_>>();
+            let converts = params.iter().map(|p| item.convert(struct_overrides, p));
+
+            if let Some(docs) = &item.docs {
+                resolved.conversion_docs = Some(format!("Elements accept {docs}."));
             }
+            resolved.mode = FieldMode::Normal {
+                generics: quote!(),
+                params: quote!(#(#params: #item_type),*),
+                assign: quote!([#(#converts),*]),
+            };
         } else if let Some(list) = overrides.list {
-            if resolved.default.is_none() {
-                let private = struct_overrides.private();
-                resolved.default = Some(quote!(#private::Default::default()));
+            resolved.collection_default(struct_overrides, list.args.capacity)?;
+            resolved.mode = FieldMode::Seq {
+                push: vec![("push", "")],
+                item: ParamConfig::new(
+                    struct_overrides,
+                    required_arg(list.args.item, list.name_span, "item")?,
+                )?,
+                from_iter: from_iter(struct_overrides, list.args.type_.as_ref()),
+                whole_into: list.args.whole_into,
             }
+        } else if let Some(deque) = overrides.deque {
+            resolved.collection_default(struct_overrides, deque.args.capacity)?;
             resolved.mode = FieldMode::Seq {
-                push: quote!(push),
-                item: ParamConfig::new(struct_overrides, list.args.item)?,
+                push: vec![
+                    ("push_back", "the back of "),
+                    ("push_front", "the front of "),
+                ],
+                item: ParamConfig::new(
+                    struct_overrides,
+                    required_arg(deque.args.item, deque.name_span, "item")?,
+                )?,
+                from_iter: from_iter(struct_overrides, deque.args.type_.as_ref()),
+                whole_into: deque.args.whole_into,
             }
         } else if let Some(set) = overrides.set {
+            resolved.collection_default(struct_overrides, set.args.capacity)?;
+            resolved.mode = FieldMode::Seq {
+                push: vec![("insert", "")],
+                item: ParamConfig::new(
+                    struct_overrides,
+                    required_arg(set.args.item, set.name_span, "item")?,
+                )?,
+                from_iter: from_iter(struct_overrides, set.args.type_.as_ref()),
+                whole_into: set.args.whole_into,
+            }
+        } else if let Some(map) = overrides.map {
+            resolved.collection_default(struct_overrides, map.args.capacity)?;
+            resolved.mode = FieldMode::Map {
+                key: ParamConfig::new(
+                    struct_overrides,
+                    required_arg(map.args.key, map.name_span, "key")?,
+                )?,
+                value: ParamConfig::new(
+                    struct_overrides,
+                    required_arg(map.args.value, map.name_span, "value")?,
+                )?,
+                from_iter: from_iter(struct_overrides, map.args.type_.as_ref()),
+            }
+        } else if let Some(string) = overrides.string {
+            let private = struct_overrides.private();
             if resolved.default.is_none() {
-                let private = struct_overrides.private();
                 resolved.default = Some(quote!(#private::Default::default()));
             }
-            resolved.mode = FieldMode::Seq {
-                push: quote!(insert),
-                item: ParamConfig::new(struct_overrides, set.args.item)?,
+            let push_type = match string.args.and_then(|a| a.push) {
+                Some(push) => {
+                    let type_ = push.args.type_;
+                    quote!(#type_)
+                }
+                None => quote!(impl #private::Display),
+            };
+            resolved.mode = FieldMode::String { push_type };
+        } else if let Some(option) = overrides.option {
+            if let Some(type_) = &option.args.type_ {
+                return Err(Error::new(
+                    type_.span(),
+                    "`option` does not support `type`; configure the item type with `item(type = ...)`",
+                ));
+            }
+            if let Some(capacity) = &option.args.capacity {
+                return Err(Error::new_spanned(
+                    capacity,
+                    "`option` does not support `capacity`",
+                ));
+            }
+            if option.args.whole_into {
+                return Err(Error::new(
+                    option.name_span,
+                    "`option` does not support `whole_into`",
+                ));
             }
-        } else if let Some(map) = overrides.map {
             if resolved.default.is_none() {
                 let private = struct_overrides.private();
-                resolved.default = Some(quote!(#private::Default::default()));
+                resolved.default = Some(quote!(#private::Option::None));
             }
-            resolved.mode = FieldMode::Map {
-                key: ParamConfig::new(struct_overrides, map.args.key)?,
-                value: ParamConfig::new(struct_overrides, map.args.value)?,
+            resolved.mode = FieldMode::Option {
+                item: ParamConfig::new(
+                    struct_overrides,
+                    required_arg(option.args.item, option.name_span, "item")?,
+                )?,
+            }
+        } else if overrides.flatten.value() {
+            if resolved.default.is_none() {
+                let private = struct_overrides.private();
+                resolved.default = Some(quote! {
+                    <#ty as #private::Flatten>::build(<#ty as #private::Flatten>::builder())
+                });
             }
+            resolved.mode = FieldMode::Flatten;
+        } else if let Some(item) = option_item(ty).filter(|_| struct_overrides.strip_option) {
+            if resolved.default.is_none() {
+                let private = struct_overrides.private();
+                resolved.default = Some(quote!(#private::Option::None));
+            }
+            resolved.mode = FieldMode::Option {
+                item: ParamConfig {
+                    type_: quote!(#item),
+                    convert: None,
+                    docs: None,
+                },
+            }
+        }
+
+        match &resolved.mode {
+            FieldMode::Seq { item, .. } => {
+                if let Some(docs) = &item.docs {
+                    resolved.conversion_docs = Some(format!("Items accept {docs}."));
+                }
+            }
+            FieldMode::Option { item } => {
+                if let Some(docs) = &item.docs {
+                    resolved.conversion_docs = Some(format!("Accepts {docs}."));
+                }
+            }
+            FieldMode::Map { key, value, .. } => {
+                let docs = [("Keys", &key.docs), ("Values", &value.docs)]
+                    .iter()
+                    .filter_map(|(kind, docs)| docs.as_ref().map(|d| format!("{kind} accept {d}.")))
+                    .collect::<Vec<_>>();
+                if !docs.is_empty() {
+                    resolved.conversion_docs = Some(docs.join(" "));
+                }
+            }
+            _ => {}
         }
 
         if let Some(stage) = overrides.stage {
             resolved.stage = stage;
         }
 
+        if let Some(rename) = overrides.name {
+            resolved.setter = rename.clone();
+            resolved.rename = Some(rename);
+        }
+
+        if let Some(source) = &resolved.default_from {
+            if !matches!(resolved.mode, FieldMode::Normal { .. }) {
+                return Err(Error::new(
+                    source.span(),
+                    "`default_from` is not supported by collection, `string`, or `option` fields",
+                ));
+            }
+        }
+
+        resolved.finalize = overrides.finalize;
+        resolved.optional_storage = match resolved.default {
+            Some(_) => {
+                lazy || resolved.default_from.is_some() || struct_overrides.trace_defaults.value()
+            }
+            None => struct_overrides.unordered.value(),
+        };
+
         Ok(resolved)
     }
 }
 
+// Fields used by `default_from` are bound to locals in `build` so their final values can be cloned into the fields
+// defaulting to them.
+fn default_from_local(name: &Ident) -> Ident {
+    Ident::new(&format!("__{}", name.unraw()), name.span())
+}
+
+// A `lazy(<expr>)` default is stored as `None` until the field is set, and only evaluated by `build` if it wasn't.
+fn lazy_default(expr: &Expr) -> Option<&Expr> {
+    match expr {
+        Expr::Call(call) if call.args.len() == 1 => match &*call.func {
+            Expr::Path(path) if path.qself.is_none() && path.path.is_ident("lazy") => {
+                call.args.first()
+            }
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
+// Fields with fallible setters store a `Result` with the struct-level error type, which must be set.
+fn fallible_error<'a>(
+    struct_overrides: &'a StructOverrides,
+    span: Span,
+    option: &str,
+) -> Result<&'a Type, Error> {
+    if struct_overrides.new.value() {
+        return Err(Error::new(
+            span,
+            format!("`{option}` cannot be combined with `new`"),
+        ));
+    }
+
+    struct_overrides.error.as_ref().ok_or_else(|| {
+        Error::new(
+            span,
+            format!("`{option}` requires the struct-level `error` option"),
+        )
+    })
+}
+
+fn is_option(ty: &Type) -> bool {
+    option_item(ty).is_some()
+}
+
+// Only the last segment of the path is checked, so `std::option::Option<T>` is detected but type aliases aren't.
+fn option_item(ty: &Type) -> Option<&Type> {
+    let path = match ty {
+        Type::Path(path) if path.qself.is_none() => &path.path,
+        _ => return None,
+    };
+
+    let segment = path.segments.last()?;
+    if segment.ident != "Option" {
+        return None;
+    }
+    match &segment.arguments {
+        PathArguments::AngleBracketed(args) if args.args.len() == 1 => match &args.args[0] {
+            GenericArgument::Type(ty) => Some(ty),
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
+fn custom_docs(type_: &Type, convert: &Convert) -> String {
+    format!(
+        "a `{}`, which is converted with `{}`",
+        tokens_docs(type_),
+        tokens_docs(convert.expr()),
+    )
+}
+
+// Token streams stringify with spaces between every token, which reads poorly in rustdoc.
+fn tokens_docs(tokens: &impl ToTokens) -> String {
+    let mut docs = tokens.to_token_stream().to_string();
+    for (from, to) in [
+        (" :: ", "::"),
+        (":: ", "::"),
+        (" < ", "<"),
+        ("< ", "<"),
+        (" >", ">"),
+        (" ,", ","),
+        ("& ", "&"),
+        (" (", "("),
+        ("( ", "("),
+        (" )", ")"),
+    ] {
+        docs = docs.replace(from, to);
+    }
+    docs
+}
+
+fn boxed_fn_mode(ty: &Type, name: &Ident) -> Result<(FieldMode, String), Error> {
+    let error = || {
+        Error::new_spanned(
+            ty,
+            "`boxed_fn` requires a field type of the form `Box<dyn Trait>`",
+        )
+    };
+
+    let path = match ty {
+        Type::Path(path) if path.qself.is_none() => &path.path,
+        _ => return Err(error()),
+    };
+    let last = path.segments.last().ok_or_else(error)?;
+    let args = match &last.arguments {
+        PathArguments::AngleBracketed(args) if args.args.len() == 1 => &args.args[0],
+        _ => return Err(error()),
+    };
+    let trait_object = match args {
+        GenericArgument::Type(Type::TraitObject(trait_object)) => trait_object,
+        _ => return Err(error()),
+    };
+
+    let bounds = &trait_object.bounds;
+    // Trait objects in a smart pointer default to a `'static` bound if not otherwise specified.
+    let static_bound = if bounds
+        .iter()
+        .any(|b| matches!(b, TypeParamBound::Lifetime(_)))
+    {
+        quote!()
+    } else {
+        quote!(+ 'static)
+    };
+
+    let mut constructor = path.clone();
+    constructor.segments.last_mut().unwrap().arguments = PathArguments::None;
+
+    let type_ = quote!(impl #bounds #static_bound);
+    let docs = format!("Accepts any `{}`, which is boxed.", tokens_docs(&type_));
+
+    let mode = FieldMode::Normal {
+        generics: quote!(),
+        params: quote!(#name: #type_),
+        assign: quote!(#constructor::new(#name)),
+    };
+
+    Ok((mode, docs))
+}
+
 #[derive(StructMeta, Default)]
 struct FieldOverrides {
     default: Option<NameValue<Option<Expr>>>,
-    into: bool,
+    into: Option<NameValue<Option<IntoValue>>>,
+    then: Option<Expr>,
     custom: Option<NameArgs<CustomOverrides>>,
     list: Option<NameArgs<SeqOverrides>>,
+    deque: Option<NameArgs<SeqOverrides>>,
     set: Option<NameArgs<SeqOverrides>>,
     map: Option<NameArgs<MapOverrides>>,
+    array: Option<NameArgs<ArrayOverrides>>,
+    string: Option<NameArgs<Option<StringOverrides>>>,
+    option: Option<NameArgs<SeqOverrides>>,
     stage: Option<Ident>,
+    name: Option<Ident>,
+    finalize: Option<Expr>,
+    generic: Option<Ident>,
+    exact: Flag,
+    boxed_fn: Flag,
+    try_into: Flag,
+    skip: Flag,
+    default_from: Option<Ident>,
+    flatten: Flag,
 }
 
 impl FieldOverrides {
+    const OPTIONS: &'static [&'static str] = &[
+        "default",
+        "into",
+        "then",
+        "custom",
+        "list",
+        "deque",
+        "set",
+        "map",
+        "array",
+        "string",
+        "option",
+        "stage",
+        "name",
+        "finalize",
+        "generic",
+        "exact",
+        "boxed_fn",
+        "try_into",
+        "skip",
+        "default_from",
+        "flatten",
+    ];
+
     fn new(attrs: &[Attribute]) -> Result<Self, Error> {
         attrs
             .iter()
             .filter(|a| a.meta.path().is_ident("builder"))
-            .map(|a| a.parse_args())
+            .map(|a| {
+                a.parse_args_with(|input: ParseStream<'_>| {
+                    syn::parse2(strip_empty_default_args(input.parse()?))
+                })
+                .map_err(|e| unknown_option_error(a, Self::OPTIONS).unwrap_or(e))
+            })
             .next()
             .transpose()
             .map(|o| o.unwrap_or_default())
     }
+
+    // Each of these options determines how the field's setters are generated, so at most one can be used.
+    fn check_conflicts(&self) -> Result<(), Error> {
+        let modes = self.modes();
+        let mut set = modes
+            .iter()
+            .filter_map(|(name, span)| span.map(|s| (name, s)));
+        if let (Some((first, _)), Some((second, span))) = (set.next(), set.next()) {
+            return Err(Error::new(
+                span,
+                format!("`{second}` cannot be combined with `{first}`"),
+            ));
+        }
+
+        Ok(())
+    }
+
+    // `into = false` doesn't determine the field's setters, so it doesn't count as a mode.
+    fn modes(&self) -> [(&'static str, Option<Span>); 12] {
+        let into = self.into.as_ref().filter(
+            |into| !matches!(&into.value, Some(IntoValue::Enabled(enabled)) if !enabled.value),
+        );
+        [
+            ("into", into.map(|o| o.name_span)),
+            ("try_into", self.try_into.span),
+            ("custom", self.custom.as_ref().map(|o| o.name_span)),
+            ("boxed_fn", self.boxed_fn.span),
+            ("array", self.array.as_ref().map(|o| o.name_span)),
+            ("list", self.list.as_ref().map(|o| o.name_span)),
+            ("deque", self.deque.as_ref().map(|o| o.name_span)),
+            ("set", self.set.as_ref().map(|o| o.name_span)),
+            ("map", self.map.as_ref().map(|o| o.name_span)),
+            ("string", self.string.as_ref().map(|o| o.name_span)),
+            ("option", self.option.as_ref().map(|o| o.name_span)),
+            ("flatten", self.flatten.span),
+        ]
+    }
+}
+
+// The value of an `into` option, which is either an intermediate type or a `bool` overriding the struct-level option.
+#[allow(clippy::large_enum_variant)]
+enum IntoValue {
+    Type(Type),
+    Enabled(LitBool),
+}
+
+impl Parse for IntoValue {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        if input.peek(LitBool) {
+            input.parse().map(IntoValue::Enabled)
+        } else {
+            input.parse().map(IntoValue::Type)
+        }
+    }
+}
+
+// structmeta can't parse an option which may be a flag, a name-value pair, or have arguments, so `default()` is
+// normalized to `default` before parsing. Only option names are considered, so expressions calling a `default`
+// function are left alone.
+fn strip_empty_default_args(tokens: TokenStream) -> TokenStream {
+    let mut out = vec![];
+    let mut tokens = tokens.into_iter().peekable();
+    let mut option_start = true;
+    while let Some(token) = tokens.next() {
+        let is_default = option_start && matches!(&token, TokenTree::Ident(i) if i == "default");
+        option_start = matches!(&token, TokenTree::Punct(p) if p.as_char() == ',');
+        out.push(token);
+        if !is_default {
+            continue;
+        }
+        if let Some(TokenTree::Group(group)) = tokens.peek() {
+            if group.delimiter() == Delimiter::Parenthesis && group.stream().is_empty() {
+                tokens.next();
+            }
+        }
+    }
+
+    out.into_iter().collect()
+}
+
+// Default expressions are evaluated in the builder's methods, so `Self` is replaced with the struct's type to keep it
+// referring to the struct as it would in the struct's own impl blocks.
+fn replace_self(tokens: TokenStream, replacement: &TokenStream) -> TokenStream {
+    tokens
+        .into_iter()
+        .map(|token| match token {
+            TokenTree::Ident(ident) if ident == "Self" => {
+                let mut group = Group::new(Delimiter::None, replacement.clone());
+                group.set_span(ident.span());
+                TokenTree::Group(group)
+            }
+            TokenTree::Group(group) => {
+                let mut new =
+                    Group::new(group.delimiter(), replace_self(group.stream(), replacement));
+                new.set_span(group.span());
+                TokenTree::Group(new)
+            }
+            token => token,
+        })
+        .collect()
+}
+
+// The errors structmeta reports for unrecognized options only suggest a single similar name, which isn't much help when
+// the option is misremembered rather than misspelled.
+fn unknown_option_error(attr: &Attribute, options: &[&str]) -> Option<Error> {
+    let names = attr
+        .parse_args_with(|input: ParseStream<'_>| {
+            let mut names = vec![];
+            while !input.is_empty() {
+                names.push(input.call(Ident::parse_any)?);
+                while !input.is_empty() && !input.peek(Token![,]) {
+                    input.parse::<TokenTree>()?;
+                }
+                if !input.is_empty() {
+                    input.parse::<Token![,]>()?;
+                }
+            }
+            Ok(names)
+        })
+        .ok()?;
+
+    let unknown = names
+        .into_iter()
+        .find(|n| !options.iter().any(|o| n == o))?;
+
+    let name = unknown.to_string();
+    let options_list = options
+        .iter()
+        .map(|o| format!("`{o}`"))
+        .collect::<Vec<_>>()
+        .join(", ");
+
+    let suggestion = options
+        .iter()
+        .map(|o| (edit_distance(&name, o), o))
+        .filter(|(distance, _)| *distance <= name.len() / 3 + 1)
+        .min_by_key(|(distance, _)| *distance);
+
+    let message = match suggestion {
+        Some((_, suggestion)) => format!(
+            "unknown option `{name}`, did you mean `{suggestion}`? Valid options are: {options_list}"
+        ),
+        None => format!("unknown option `{name}`. Valid options are: {options_list}"),
+    };
+
+    Some(Error::new(unknown.span(), message))
+}
+
+fn edit_distance(a: &str, b: &str) -> usize {
+    let b = b.chars().collect::<Vec<_>>();
+    let mut prev = (0..=b.len()).collect::<Vec<_>>();
+
+    for (i, ca) in a.chars().enumerate() {
+        let mut cur = vec![i + 1];
+        for (j, cb) in b.iter().enumerate() {
+            let substitution = prev[j] + usize::from(ca != *cb);
+            cur.push(substitution.min(prev[j + 1] + 1).min(cur[j] + 1));
+        }
+        prev = cur;
+    }
+
+    prev[b.len()]
 }
 
 #[derive(StructMeta)]
 struct CustomOverrides {
     #[struct_meta(name = "type")]
-    type_: Type,
-    convert: Expr,
+    type_: Option<Type>,
+    convert: Option<Expr>,
+    arg: Option<Ident>,
+    fallible: Flag,
+}
+
+// Both arguments are parsed as optional so that a missing argument can be reported at the `custom(...)` attribute
+// rather than at the macro invocation.
+fn resolve_custom(custom: NameArgs<CustomOverrides>) -> Result<(Type, Convert), Error> {
+    let type_ = custom
+        .args
+        .type_
+        .ok_or_else(|| Error::new(custom.name_span, "missing argument `type = ...`"))?;
+    let convert = custom
+        .args
+        .convert
+        .ok_or_else(|| Error::new(custom.name_span, "missing argument `convert = ...`"))?;
+
+    let convert = match custom.args.arg {
+        Some(arg) => Convert::Binding { arg, expr: convert },
+        None => Convert::Callable(convert),
+    };
+
+    Ok((type_, convert))
+}
+
+// Like `custom`, the item configuration of collections is parsed as optional so that its absence is reported at the
+// collection's attribute.
+fn required_arg(
+    arg: Option<NameArgs<ParamOverrides>>,
+    span: Span,
+    name: &str,
+) -> Result<NameArgs<ParamOverrides>, Error> {
+    arg.ok_or_else(|| Error::new(span, format!("missing argument `{name}(...)`")))
 }
 
 #[derive(StructMeta)]
 struct SeqOverrides {
-    item: NameArgs<ParamOverrides>,
+    #[struct_meta(name = "type")]
+    type_: Option<Type>,
+    capacity: Option<Expr>,
+    whole_into: bool,
+    item: Option<NameArgs<ParamOverrides>>,
 }
 
 #[derive(StructMeta)]
@@ -1040,11 +4442,186 @@ struct ParamOverrides {
     #[struct_meta(name = "type")]
     type_: Option<Type>,
     into: bool,
+    cloned: bool,
     custom: Option<NameArgs<CustomOverrides>>,
+    list: Option<NameArgs<Box<SeqOverrides>>>,
+    set: Option<NameArgs<Box<SeqOverrides>>>,
+}
+
+#[derive(StructMeta)]
+struct ArrayOverrides {
+    len: LitInt,
+    item: Option<NameArgs<ParamOverrides>>,
+}
+
+#[derive(StructMeta)]
+struct StringOverrides {
+    push: Option<NameArgs<StringPushOverrides>>,
+}
+
+#[derive(StructMeta)]
+struct StringPushOverrides {
+    #[struct_meta(name = "type")]
+    type_: Type,
 }
 
 #[derive(StructMeta)]
 struct MapOverrides {
-    key: NameArgs<ParamOverrides>,
-    value: NameArgs<ParamOverrides>,
+    #[struct_meta(name = "type")]
+    type_: Option<Type>,
+    capacity: Option<Expr>,
+    key: Option<NameArgs<ParamOverrides>>,
+    value: Option<NameArgs<ParamOverrides>>,
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn inline_setters_required_only() {
+        let input = syn::parse_quote! {
+            #[builder(inline_setters = required_only)]
+            struct Foo {
+                a: i32,
+                #[builder(default)]
+                b: i32,
+            }
+        };
+        let tokens = expand(input).unwrap().to_string();
+
+        assert!(tokens.contains("# [inline] pub fn a ("));
+        assert!(tokens.contains("pub fn b ("));
+        assert!(!tokens.contains("# [inline] pub fn b ("));
+    }
+
+    #[test]
+    fn conversion_docs() {
+        let input = syn::parse_quote! {
+            struct Foo {
+                /// The name.
+                #[builder(into)]
+                name: String,
+                #[builder(list(item(type = String, into)))]
+                aliases: Vec<String>,
+            }
+        };
+        let tokens = expand(input).unwrap().to_string();
+
+        assert!(tokens.contains(concat!(
+            r#"# [doc = r" The name."] # [doc = ""] # [doc = "Sets the `name` field."] "#,
+            r#"# [doc = ""] # [doc = "Accepts any `impl Into<String>`."]"#,
+        )));
+        assert!(tokens.contains(r#"# [doc = "Items accept any `impl Into<String>`."]"#));
+    }
+
+    #[test]
+    fn raw_ident_docs() {
+        let input = syn::parse_quote! {
+            #[builder(getters, trace_defaults)]
+            struct Foo {
+                #[builder(default)]
+                r#type: u32,
+            }
+        };
+        let tokens = expand(input).unwrap().to_string();
+
+        assert!(!tokens.contains("r#type`"));
+        assert!(tokens.contains(r#"# [doc = "Sets the `type` field."]"#));
+        assert!(tokens.contains(r#"# [doc = "Returns the current value of the `type` field."]"#));
+        assert!(tokens.contains(r#""`type` field of `Foo` was not set; using its default value""#));
+
+        let input = syn::parse_quote! {
+            #[builder(deref_view)]
+            struct Foo {
+                #[builder(default)]
+                r#type: u32,
+            }
+        };
+        let tokens = expand(input).unwrap().to_string();
+
+        assert!(tokens.contains(r#"# [doc = "The current value of the `type` field."]"#));
+    }
+
+    #[test]
+    fn conflicting_field_options() {
+        let cases: &[(syn::Attribute, &str)] = &[
+            (
+                syn::parse_quote!(#[builder(into, list(item(type = u32)))]),
+                "`list` cannot be combined with `into`",
+            ),
+            (
+                syn::parse_quote!(#[builder(into, custom(type = u32, convert = From::from))]),
+                "`custom` cannot be combined with `into`",
+            ),
+            (
+                syn::parse_quote!(#[builder(custom(type = u32, convert = From::from), set(item(type = u32)))]),
+                "`set` cannot be combined with `custom`",
+            ),
+            (
+                syn::parse_quote!(#[builder(list(item(type = u32)), set(item(type = u32)))]),
+                "`set` cannot be combined with `list`",
+            ),
+            (
+                syn::parse_quote!(#[builder(map(key(type = u32), value(type = u32)), into)]),
+                "`map` cannot be combined with `into`",
+            ),
+        ];
+
+        for (attr, message) in cases {
+            let input = syn::parse_quote! {
+                struct Foo {
+                    #attr
+                    a: Vec<u32>,
+                }
+            };
+            let error = expand(input).unwrap_err();
+            assert_eq!(error.to_string(), *message);
+        }
+    }
+
+    #[test]
+    fn field_docs_forwarded() {
+        let input = syn::parse_quote! {
+            struct Foo {
+                /// Line one.
+                /// Line two.
+                #[doc(hidden)]
+                a: i32,
+            }
+        };
+        let tokens = expand(input).unwrap().to_string();
+
+        assert!(tokens.contains(concat!(
+            r#"# [doc = r" Line one."] # [doc = r" Line two."] # [doc (hidden)] "#,
+            r#"# [doc = ""] # [doc = "Sets the `a` field."]"#,
+        )));
+    }
+
+    // Set `SNAPSHOT=overwrite` to regenerate the expected output.
+    #[test]
+    fn expansion_snapshot() {
+        let input = syn::parse_quote! {
+            #[builder(update)]
+            pub struct Person {
+                #[builder(into)]
+                name: String,
+                age: u32,
+                #[builder(default)]
+                nickname: Option<String>,
+                #[builder(list(item(type = String)))]
+                aliases: Vec<String>,
+            }
+        };
+        let tokens = expand(input).unwrap();
+        let actual = prettyplease::unparse(&syn::parse2(tokens).unwrap());
+
+        let path = concat!(env!("CARGO_MANIFEST_DIR"), "/tests/snapshots/person.rs");
+        if std::env::var_os("SNAPSHOT").map_or(false, |v| v == "overwrite") {
+            std::fs::write(path, &actual).unwrap();
+        }
+
+        let expected = std::fs::read_to_string(path).unwrap();
+        assert_eq!(actual, expected);
+    }
 }
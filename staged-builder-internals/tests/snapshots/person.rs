@@ -0,0 +1,203 @@
+impl Person {
+    ///Returns a new builder.
+    #[inline]
+    #[allow(clippy::type_complexity)]
+    pub fn builder() -> person::Builder<person::NameStage> {
+        ::staged_builder::__private::Default::default()
+    }
+}
+///Builder types for [`Person`].
+pub mod person {
+    use super::*;
+    ///A builder for [`Person`](super::Person)
+    #[must_use = "builders do nothing unless built"]
+    pub struct Builder<T>(T);
+    impl<T> Builder<T> {
+        /// The number of required fields which must be set before the value can be built.
+        pub const REQUIRED_FIELD_COUNT: usize = 2usize;
+    }
+    impl ::staged_builder::__private::Default for Builder<NameStage> {
+        #[inline]
+        fn default() -> Self {
+            Builder(NameStage {})
+        }
+    }
+    ///The `name` stage for [`Builder`].
+    #[allow(clippy::type_complexity)]
+    pub struct NameStage {}
+    #[allow(clippy::type_complexity, clippy::redundant_field_names)]
+    impl Builder<NameStage> {
+        ///Sets the `name` field.
+        ///
+        ///Accepts any `impl Into<String>`.
+        #[inline]
+        pub fn name(
+            self,
+            name: impl ::staged_builder::__private::Into<String>,
+        ) -> Builder<AgeStage> {
+            Builder(AgeStage {
+                name: ::staged_builder::__private::Into::<String>::into(name),
+            })
+        }
+    }
+    ///The `age` stage for [`Builder`].
+    #[allow(clippy::type_complexity)]
+    pub struct AgeStage {
+        name: String,
+    }
+    #[allow(clippy::type_complexity, clippy::redundant_field_names)]
+    impl Builder<AgeStage> {
+        ///Sets the `age` field.
+        #[inline]
+        pub fn age(self, age: u32) -> Builder<Complete> {
+            Builder(Complete {
+                name: self.0.name,
+                age: age,
+                nickname: ::staged_builder::__private::Default::default(),
+                aliases: ::staged_builder::__private::Default::default(),
+            })
+        }
+    }
+    ///The final stage for [`Person`](super::Person).
+    #[allow(clippy::type_complexity)]
+    pub struct Complete {
+        ///The current value of the `name` field.
+        name: String,
+        ///The current value of the `age` field.
+        age: u32,
+        ///The current value of the `nickname` field.
+        nickname: Option<String>,
+        ///The current value of the `aliases` field.
+        aliases: Vec<String>,
+    }
+    #[allow(clippy::type_complexity)]
+    impl Builder<Complete> {
+        ///Sets the `name` field.
+        ///
+        ///Accepts any `impl Into<String>`.
+        #[inline]
+        pub fn name(
+            mut self,
+            name: impl ::staged_builder::__private::Into<String>,
+        ) -> Self {
+            self.0.name = ::staged_builder::__private::Into::<String>::into(name);
+            self
+        }
+        ///Sets the `age` field.
+        #[inline]
+        pub fn age(mut self, age: u32) -> Self {
+            self.0.age = age;
+            self
+        }
+        ///Sets the `nickname` field.
+        #[inline]
+        pub fn nickname(mut self, nickname: Option<String>) -> Self {
+            self.0.nickname = nickname;
+            self
+        }
+        ///Resets the `nickname` field to its default value.
+        #[inline]
+        pub fn reset_nickname(mut self) -> Self {
+            self.0.nickname = ::staged_builder::__private::Default::default();
+            self
+        }
+        ///Transforms the current value of the `nickname` field, which is its default value if it hasn't been set.
+        #[inline]
+        pub fn map_nickname(
+            mut self,
+            f: impl FnOnce(Option<String>) -> Option<String>,
+        ) -> Self {
+            self.0.nickname = f(self.0.nickname);
+            self
+        }
+        ///Adds a value to the `aliases` field.
+        #[inline]
+        pub fn push_aliases(mut self, aliases: String) -> Self {
+            (&mut self.0.aliases).push(aliases);
+            self
+        }
+        ///Sets the `aliases` field.
+        #[inline]
+        pub fn aliases(
+            mut self,
+            aliases: impl ::staged_builder::__private::IntoIterator<Item = String>,
+        ) -> Self {
+            self.0.aliases = ::staged_builder::__private::FromIterator::from_iter(
+                aliases,
+            );
+            self
+        }
+        ///Adds values to the `aliases` field.
+        #[inline]
+        pub fn extend_aliases(
+            mut self,
+            aliases: impl ::staged_builder::__private::IntoIterator<Item = String>,
+        ) -> Self {
+            ::staged_builder::__private::Extend::extend(&mut self.0.aliases, aliases);
+            self
+        }
+        ///Resets the `aliases` field to its default value.
+        #[inline]
+        pub fn reset_aliases(mut self) -> Self {
+            self.0.aliases = ::staged_builder::__private::Default::default();
+            self
+        }
+        ///Transforms the current value of the `aliases` field, which is its default value if it hasn't been set.
+        #[inline]
+        pub fn map_aliases(
+            mut self,
+            f: impl FnOnce(Vec<String>) -> Vec<String>,
+        ) -> Self {
+            self.0.aliases = f(self.0.aliases);
+            self
+        }
+        ///Consumes the builder, returning a [`Person`](super::Person).
+        #[inline]
+        pub fn build(self) -> super::Person {
+            self.build_with(|_| {})
+        }
+        ///Consumes the builder, returning a [`Person`](super::Person) after passing it to a closure for modification.
+        #[inline]
+        pub fn build_with(self, f: impl FnOnce(&mut super::Person)) -> super::Person {
+            let mut value = super::Person {
+                name: self.0.name,
+                age: self.0.age,
+                nickname: self.0.nickname,
+                aliases: self.0.aliases,
+            };
+            f(&mut value);
+            value
+        }
+        /**Consumes the builder, returning a [`Person`](super::Person).
+
+Unlike `build`, this always returns a `Result`.*/
+        #[inline]
+        pub fn try_build(
+            self,
+        ) -> ::staged_builder::__private::Result<
+            super::Person,
+            ::staged_builder::__private::Infallible,
+        > {
+            ::staged_builder::__private::Result::Ok(self.build())
+        }
+        ///Consumes the builder, returning a value converted from a [`Person`](super::Person).
+        #[inline]
+        pub fn build_into<T>(self) -> T
+        where
+            T: ::staged_builder::__private::From<super::Person>,
+        {
+            ::staged_builder::__private::From::from(self.build())
+        }
+    }
+    impl ::staged_builder::__private::From<super::Person> for Builder<Complete> {
+        #[inline]
+        fn from(v: super::Person) -> Self {
+            Builder(Complete {
+                name: v.name,
+                age: v.age,
+                nickname: v.nickname,
+                aliases: v.aliases,
+            })
+        }
+    }
+}
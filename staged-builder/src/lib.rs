@@ -5,7 +5,8 @@
 //! corresponding to a required field of the struct. The final stage has setters for all optional fields and the final
 //! `.build()` method.
 //!
-//! See the documentation for [`#[staged_builder]`](staged_builder) for more details.
+//! See the documentation for [`#[staged_builder]`](staged_builder) for more details. The builder can also be
+//! generated with [`#[derive(StagedBuilder)]`](StagedBuilder), which is equivalent.
 //!
 //! # Examples
 //!
@@ -30,24 +31,99 @@
 //! ```
 #![cfg_attr(not(doc), no_std)]
 
+#[cfg(feature = "alloc")]
+extern crate alloc;
+
+use core::fmt;
+
 // Not part of the public API.
 #[doc(hidden)]
 pub use staged_builder_internals::__StagedBuilderInternalDerive;
 #[doc(inline)]
 pub use staged_builder_internals::staged_builder;
+#[doc(inline)]
+pub use staged_builder_internals::StagedBuilder;
 
 // Not part of the public API.
 #[doc(hidden)]
 pub mod __private {
-    pub use core::convert::{From, Into};
+    #[cfg(feature = "alloc")]
+    pub use alloc::boxed::Box;
+    #[cfg(feature = "alloc")]
+    pub use alloc::rc::Rc;
+    #[cfg(all(feature = "alloc", target_has_atomic = "ptr"))]
+    pub use alloc::sync::Arc;
+    pub use core::any::Any;
+    pub use core::borrow::Borrow;
+    pub use core::clone::Clone;
+    pub use core::cmp::{Eq, PartialEq};
+    pub use core::convert::{From, Infallible, Into, TryInto};
     pub use core::default::Default;
+    pub use core::fmt::{self, Display, Write};
+    pub use core::format_args;
     pub use core::iter::{Extend, FromIterator, IntoIterator, Iterator};
+    pub use core::marker::PhantomData;
+    pub use core::ops::Deref;
+    pub use core::option::Option;
     pub use core::result::Result;
+    #[cfg(feature = "tracing")]
+    pub use tracing;
+
+    pub trait TryIntoField<T, E> {
+        fn try_into_field(self) -> Result<T, E>;
+    }
+
+    impl<T, U, E> TryIntoField<T, E> for U
+    where
+        U: TryInto<T>,
+        E: From<U::Error>,
+    {
+        #[inline]
+        fn try_into_field(self) -> Result<T, E> {
+            TryInto::try_into(self).map_err(E::from)
+        }
+    }
+
+    pub trait Flatten: Sized {
+        type Builder;
+
+        fn builder() -> Self::Builder;
+
+        fn build(builder: Self::Builder) -> Self;
+
+        fn into_builder(self) -> Self::Builder;
+
+        #[inline]
+        fn update(&mut self, f: impl FnOnce(Self::Builder) -> Self::Builder) {
+            let value = core::mem::replace(self, Self::build(Self::builder()));
+            *self = Self::build(f(value.into_builder()));
+        }
+    }
 
     #[inline]
     pub fn call_hack<T, R>(f: impl FnOnce(T) -> R, v: T) -> R {
         f(v)
     }
+
+    #[inline]
+    pub fn missing_fields(
+        fields: &'static [&'static str],
+        missing: &[bool],
+    ) -> crate::MissingFields {
+        let mut mask = 0;
+        for (i, missing) in missing.iter().enumerate() {
+            if *missing {
+                mask |= 1 << i;
+            }
+        }
+
+        crate::MissingFields { fields, mask }
+    }
+
+    #[inline]
+    pub fn not_distinct(first: &'static str, second: &'static str) -> crate::NotDistinct {
+        crate::NotDistinct { first, second }
+    }
 }
 
 /// A trait for types which validate their state before construction finishes.
@@ -61,6 +137,90 @@ pub trait Validate {
     fn validate(&self) -> Result<(), Self::Error>;
 }
 
+/// A trait for types which normalize their state before construction finishes.
+///
+/// The generated builder will call this method if the `#[builder(normalize)]` attribute is placed at the struct level.
+/// Unlike [`Validate`], the value is taken by value and can be transformed.
+pub trait Normalize: Sized {
+    /// The error returned for a value which can't be normalized.
+    type Error;
+
+    /// Normalizes `self`, returning the normalized value.
+    fn normalize(self) -> Result<Self, Self::Error>;
+}
+
+/// The error returned when fields required to be distinct by the `#[builder(distinct(...))]` attribute are equal.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NotDistinct {
+    first: &'static str,
+    second: &'static str,
+}
+
+impl NotDistinct {
+    /// Returns the name of the first of the two equal fields.
+    pub fn first(&self) -> &'static str {
+        self.first
+    }
+
+    /// Returns the name of the second of the two equal fields.
+    pub fn second(&self) -> &'static str {
+        self.second
+    }
+}
+
+impl fmt::Display for NotDistinct {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "fields `{}` and `{}` must be distinct",
+            self.first, self.second
+        )
+    }
+}
+
+/// The error returned when required fields were not set on a builder generated with the `#[builder(unordered)]`
+/// attribute.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MissingFields {
+    fields: &'static [&'static str],
+    mask: u64,
+}
+
+impl MissingFields {
+    /// Returns an iterator over the names of the required fields that were not set.
+    pub fn fields(&self) -> impl Iterator<Item = &'static str> + '_ {
+        self.fields
+            .iter()
+            .enumerate()
+            .filter(move |(i, _)| self.mask & (1 << i) != 0)
+            .map(|(_, name)| *name)
+    }
+}
+
+impl fmt::Display for MissingFields {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("missing required fields: ")?;
+        for (i, name) in self.fields().enumerate() {
+            if i != 0 {
+                f.write_str(", ")?;
+            }
+            write!(f, "`{}`", name)?;
+        }
+        Ok(())
+    }
+}
+
+/// The error returned when a `PartialBuilder` generated by the `#[builder(dynamic)]` attribute is passed a field name
+/// and value type that do not match any field.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UnknownField;
+
+impl fmt::Display for UnknownField {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("unknown field")
+    }
+}
+
 /// An example type using [`#[staged_builder]`](staged_builder).
 #[cfg(doc)]
 #[staged_builder]
@@ -0,0 +1,93 @@
+#![cfg(feature = "alloc")]
+
+use staged_builder::staged_builder;
+use std::rc::Rc;
+use std::sync::Arc;
+
+#[derive(PartialEq, Debug)]
+#[staged_builder]
+#[builder(boxed)]
+struct Boxed {
+    a: i32,
+    #[builder(default)]
+    children: Vec<Boxed>,
+}
+
+#[test]
+fn build_boxed() {
+    let actual = Boxed::builder().a(1).build_boxed();
+    assert_eq!(
+        actual,
+        Box::new(Boxed {
+            a: 1,
+            children: vec![],
+        })
+    );
+}
+
+#[derive(PartialEq, Debug)]
+#[staged_builder]
+#[builder(
+    boxed,
+    validate = |s: &BoxedValidated| -> Result<(), &'static str> {
+        if s.a > 0 {
+            Ok(())
+        } else {
+            Err("not positive")
+        }
+    }
+)]
+struct BoxedValidated {
+    a: i32,
+}
+
+#[test]
+fn build_boxed_validated() {
+    let actual = BoxedValidated::builder().a(1).build_boxed();
+    assert_eq!(actual, Ok(Box::new(BoxedValidated { a: 1 })));
+
+    let actual = BoxedValidated::builder().a(0).build_boxed();
+    assert_eq!(actual, Err("not positive"));
+}
+
+#[derive(PartialEq, Debug)]
+#[staged_builder]
+#[builder(rc, arc)]
+struct Shared {
+    a: i32,
+}
+
+#[test]
+fn build_shared() {
+    let actual = Shared::builder().a(1).build_rc();
+    assert_eq!(actual, Rc::new(Shared { a: 1 }));
+
+    let actual = Shared::builder().a(2).build_arc();
+    assert_eq!(actual, Arc::new(Shared { a: 2 }));
+}
+
+#[derive(PartialEq, Debug)]
+#[staged_builder]
+#[builder(
+    rc,
+    arc,
+    validate = |s: &SharedValidated| -> Result<(), &'static str> {
+        if s.a > 0 {
+            Ok(())
+        } else {
+            Err("not positive")
+        }
+    }
+)]
+struct SharedValidated {
+    a: i32,
+}
+
+#[test]
+fn build_shared_validated() {
+    let actual = SharedValidated::builder().a(1).build_rc();
+    assert_eq!(actual, Ok(Rc::new(SharedValidated { a: 1 })));
+
+    let actual = SharedValidated::builder().a(0).build_arc();
+    assert_eq!(actual, Err("not positive"));
+}
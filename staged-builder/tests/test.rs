@@ -1,6 +1,10 @@
-use staged_builder::{staged_builder, Validate};
-use std::collections::{HashMap, HashSet};
+use staged_builder::{staged_builder, Normalize, StagedBuilder, Validate};
+use std::borrow::Cow;
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet, VecDeque};
+use std::convert::Infallible;
 use std::fmt::Display;
+use std::num::{ParseIntError, TryFromIntError};
+use std::sync::atomic::{AtomicUsize, Ordering};
 
 #[derive(PartialEq, Debug)]
 #[staged_builder]
@@ -50,6 +54,21 @@ fn validate() {
     Validated::builder().even(1).build().err().unwrap();
 }
 
+#[test]
+fn try_build() {
+    Validated::builder().even(0).try_build().unwrap();
+    Validated::builder().even(1).try_build().err().unwrap();
+
+    let actual: Result<Foo, Infallible> = Foo::builder().required(true).required2("a").try_build();
+    let expected = Foo {
+        required: true,
+        required2: "a".to_string(),
+        normal_default: "".to_string(),
+        custom_default: 42,
+    };
+    assert_eq!(actual, Ok(expected));
+}
+
 #[derive(PartialEq, Debug)]
 #[staged_builder]
 struct Collections {
@@ -207,6 +226,25 @@ mod inline {
         let expected = Inline { a: 1 };
         assert_eq!(actual, expected);
     }
+
+    mod private {
+        use staged_builder::staged_builder;
+
+        #[staged_builder]
+        #[builder(inline)]
+        struct Private {
+            a: i32,
+        }
+
+        pub fn build(a: i32) -> i32 {
+            Private::builder().a(a).build().a
+        }
+    }
+
+    #[test]
+    fn inline_private() {
+        assert_eq!(private::build(1), 1);
+    }
 }
 
 #[derive(PartialEq, Debug)]
@@ -245,3 +283,2280 @@ fn update() {
     };
     assert_eq!(actual, expected);
 }
+
+#[derive(PartialEq, Debug)]
+#[staged_builder]
+#[builder(
+    update,
+    complete = MyComplete,
+    validate = |s: &UpdateValidated| -> Result<(), &'static str> {
+        if s.a < s.b {
+            Ok(())
+        } else {
+            Err("a >= b")
+        }
+    }
+)]
+struct UpdateValidated {
+    a: i32,
+    b: i32,
+}
+
+#[test]
+fn update_validated() {
+    let v = UpdateValidated::builder().a(1).b(2).build().unwrap();
+    let builder: update_validated::Builder<update_validated::MyComplete> =
+        update_validated::Builder::from(v);
+    let actual = builder.b(3).build().unwrap();
+    assert_eq!(actual, UpdateValidated { a: 1, b: 3 });
+
+    let err = update_validated::Builder::from(actual).a(4).build().err();
+    assert_eq!(err, Some("a >= b"));
+}
+
+trait Configurable {
+    type Builder;
+    type Complete;
+
+    fn builder() -> Self::Builder;
+}
+
+#[derive(PartialEq, Debug)]
+#[staged_builder]
+#[builder(impl_trait = Configurable)]
+struct ConfigurableA {
+    a: i32,
+}
+
+#[derive(PartialEq, Debug)]
+#[staged_builder]
+#[builder(impl_trait = Configurable)]
+struct ConfigurableB {
+    #[builder(default)]
+    b: bool,
+}
+
+fn configure<T>() -> T::Builder
+where
+    T: Configurable,
+{
+    T::builder()
+}
+
+#[test]
+fn impl_trait() {
+    let actual = configure::<ConfigurableA>().a(1).build();
+    assert_eq!(actual, ConfigurableA { a: 1 });
+
+    let stage: <ConfigurableB as Configurable>::Complete = configure::<ConfigurableB>();
+    let actual = stage.b(true).build();
+    assert_eq!(actual, ConfigurableB { b: true });
+}
+
+#[derive(PartialEq, Debug)]
+#[staged_builder]
+struct Finalize {
+    #[builder(into, finalize = |s: String| s.to_lowercase())]
+    name: String,
+    #[builder(default, finalize = i32::abs)]
+    value: i32,
+}
+
+#[test]
+fn finalize() {
+    let actual = Finalize::builder().name("HeLLo").value(-4).build();
+    let expected = Finalize {
+        name: "hello".to_string(),
+        value: 4,
+    };
+    assert_eq!(actual, expected);
+}
+
+#[derive(PartialEq, Debug)]
+struct IndexMap<K, V>(Vec<(K, V)>);
+
+impl<K, V> Default for IndexMap<K, V> {
+    fn default() -> Self {
+        IndexMap(vec![])
+    }
+}
+
+impl<K, V> IndexMap<K, V>
+where
+    K: PartialEq,
+{
+    fn insert(&mut self, key: K, value: V) -> Option<V> {
+        match self.0.iter_mut().find(|(k, _)| *k == key) {
+            Some((_, v)) => Some(std::mem::replace(v, value)),
+            None => {
+                self.0.push((key, value));
+                None
+            }
+        }
+    }
+}
+
+impl<K, V> FromIterator<(K, V)> for IndexMap<K, V>
+where
+    K: PartialEq,
+{
+    fn from_iter<T>(iter: T) -> Self
+    where
+        T: IntoIterator<Item = (K, V)>,
+    {
+        let mut map = IndexMap::default();
+        map.extend(iter);
+        map
+    }
+}
+
+impl<K, V> Extend<(K, V)> for IndexMap<K, V>
+where
+    K: PartialEq,
+{
+    fn extend<T>(&mut self, iter: T)
+    where
+        T: IntoIterator<Item = (K, V)>,
+    {
+        for (k, v) in iter {
+            self.insert(k, v);
+        }
+    }
+}
+
+#[derive(PartialEq, Debug)]
+#[staged_builder]
+struct IndexMapOrder {
+    #[builder(map(key(type = &'static str), value(type = i32)))]
+    map: IndexMap<&'static str, i32>,
+}
+
+#[test]
+fn index_map_order() {
+    let expected = IndexMapOrder {
+        map: IndexMap(vec![("c", 1), ("a", 2), ("b", 3)]),
+    };
+
+    let actual = IndexMapOrder::builder()
+        .insert_map("c", 1)
+        .insert_map("a", 2)
+        .insert_map("b", 3)
+        .build();
+    assert_eq!(actual, expected);
+
+    let actual = IndexMapOrder::builder()
+        .insert_map("z", 0)
+        .map([("c", 1), ("a", 2), ("b", 3)])
+        .build();
+    assert_eq!(actual, expected);
+
+    let actual = IndexMapOrder::builder()
+        .insert_map("c", 1)
+        .extend_map([("a", 2), ("b", 3)])
+        .build();
+    assert_eq!(actual, expected);
+}
+
+mod encapsulated {
+    use staged_builder::staged_builder;
+
+    #[staged_builder]
+    pub struct PrivateFields {
+        a: i32,
+        #[builder(default)]
+        b: bool,
+    }
+
+    impl PrivateFields {
+        pub fn a(&self) -> i32 {
+            self.a
+        }
+
+        pub fn b(&self) -> bool {
+            self.b
+        }
+    }
+
+    #[staged_builder]
+    #[builder(inline)]
+    pub(crate) struct InlinePrivateFields {
+        a: i32,
+    }
+
+    impl InlinePrivateFields {
+        pub fn a(&self) -> i32 {
+            self.a
+        }
+    }
+}
+
+#[test]
+fn private_fields() {
+    let stage: encapsulated::private_fields::Builder<encapsulated::private_fields::Complete> =
+        encapsulated::PrivateFields::builder().a(1);
+    let actual = stage.b(true).build();
+    assert_eq!(actual.a(), 1);
+    assert!(actual.b());
+
+    let stage: encapsulated::Builder<encapsulated::Complete> =
+        encapsulated::InlinePrivateFields::builder().a(2);
+    assert_eq!(stage.build().a(), 2);
+}
+
+#[derive(PartialEq, Debug)]
+#[staged_builder]
+struct NamedGeneric {
+    #[builder(into, generic = S)]
+    a: String,
+    #[builder(default, into, generic = T)]
+    b: Option<u32>,
+}
+
+#[test]
+fn named_generic() {
+    let actual = NamedGeneric::builder()
+        .a::<&str>("hello")
+        .b::<u32>(1)
+        .build();
+    let expected = NamedGeneric {
+        a: "hello".to_string(),
+        b: Some(1),
+    };
+    assert_eq!(actual, expected);
+}
+
+#[derive(PartialEq, Debug)]
+#[staged_builder]
+#[builder(new)]
+struct NewtypeTuple(#[builder(into)] String);
+
+#[derive(PartialEq, Debug)]
+#[staged_builder]
+#[builder(new)]
+struct NewtypeNamed {
+    #[builder(custom(type = u32, convert = i64::from))]
+    value: i64,
+}
+
+#[test]
+fn newtype() {
+    assert_eq!(NewtypeTuple::new("hi"), NewtypeTuple("hi".to_string()));
+    assert_eq!(NewtypeNamed::new(1), NewtypeNamed { value: 1 });
+}
+
+#[derive(PartialEq, Debug)]
+#[staged_builder]
+#[builder(mod_suffix = "_builder")]
+struct Person {
+    name: &'static str,
+}
+
+#[test]
+fn mod_suffix() {
+    let stage: person_builder::Builder<person_builder::Complete> = Person::builder().name("Jane");
+    assert_eq!(stage.build(), Person { name: "Jane" });
+}
+
+#[derive(PartialEq, Debug)]
+#[staged_builder]
+#[builder(distinct(from, to))]
+struct Edge {
+    from: u32,
+    to: u32,
+}
+
+#[test]
+fn distinct() {
+    let actual = Edge::builder().from(1).to(2).build().unwrap();
+    assert_eq!(actual, Edge { from: 1, to: 2 });
+
+    let err = Edge::builder().from(1).to(1).build().unwrap_err();
+    assert_eq!(err.first(), "from");
+    assert_eq!(err.second(), "to");
+    assert_eq!(err.to_string(), "fields `from` and `to` must be distinct");
+}
+
+#[derive(Debug, PartialEq)]
+enum EdgeError {
+    Missing(staged_builder::MissingFields),
+    NotDistinct(staged_builder::NotDistinct),
+}
+
+impl From<staged_builder::MissingFields> for EdgeError {
+    fn from(e: staged_builder::MissingFields) -> Self {
+        EdgeError::Missing(e)
+    }
+}
+
+impl From<staged_builder::NotDistinct> for EdgeError {
+    fn from(e: staged_builder::NotDistinct) -> Self {
+        EdgeError::NotDistinct(e)
+    }
+}
+
+#[derive(PartialEq, Debug)]
+#[staged_builder]
+#[builder(unordered, distinct(from, to), error = EdgeError)]
+struct UnorderedEdge {
+    from: u32,
+    to: u32,
+}
+
+#[test]
+fn unordered_distinct() {
+    let actual = UnorderedEdge::builder().to(2).from(1).build().unwrap();
+    assert_eq!(actual, UnorderedEdge { from: 1, to: 2 });
+
+    let err = UnorderedEdge::builder().to(2).build().unwrap_err();
+    assert!(matches!(err, EdgeError::Missing(e) if e.fields().eq(["from"])));
+
+    let err = UnorderedEdge::builder().to(1).from(1).build().unwrap_err();
+    assert!(matches!(err, EdgeError::NotDistinct(e) if e.first() == "from"));
+}
+
+#[allow(clippy::type_complexity)]
+#[staged_builder]
+struct LintProne {
+    to_x: u32,
+    from_y: u32,
+    #[builder(default)]
+    as_z: u32,
+    #[builder(default)]
+    is_w: bool,
+    #[builder(default)]
+    callback: Option<Box<dyn Fn(Vec<(u32, String)>) -> Result<Vec<Box<dyn Fn()>>, String>>>,
+}
+
+fn lint_prone_stage() -> lint_prone::Builder<lint_prone::Complete> {
+    LintProne::builder().to_x(1).from_y(2)
+}
+
+#[test]
+fn lint_prone() {
+    let v = lint_prone_stage()
+        .as_z(3)
+        .is_w(true)
+        .callback(Some(Box::new(|_| Ok(vec![]))))
+        .build();
+    assert!(v.callback.is_some());
+    assert_eq!((v.to_x, v.from_y, v.as_z, v.is_w), (1, 2, 3, true));
+}
+
+mod complex_first {
+    #![deny(clippy::type_complexity)]
+
+    use staged_builder::staged_builder;
+
+    #[allow(clippy::type_complexity)]
+    #[staged_builder]
+    pub struct ComplexFirst {
+        pub callback: Box<dyn Fn(Vec<(u32, String)>) -> Result<Vec<Box<dyn Fn()>>, String>>,
+        pub id: u32,
+    }
+
+    pub fn stage() -> complex_first::Builder<complex_first::IdStage> {
+        ComplexFirst::builder().callback(Box::new(|_| Ok(vec![])))
+    }
+}
+
+#[test]
+fn complex_first() {
+    let v = complex_first::stage().id(1).build();
+    assert!((v.callback)(vec![]).unwrap().is_empty());
+    assert_eq!(v.id, 1);
+}
+
+#[allow(dead_code)]
+#[staged_builder]
+#[builder(optional_field_docs)]
+struct OptionalFieldDocs {
+    required: u32,
+    #[builder(default)]
+    plain: u32,
+    #[builder(default = 42)]
+    custom: u32,
+    #[builder(list(item(type = u32)))]
+    list: Vec<u32>,
+}
+
+#[test]
+fn optional_field_docs() {
+    assert_eq!(
+        OptionalFieldDocs::OPTIONAL_FIELDS,
+        &[
+            ("plain", "Default::default()"),
+            ("custom", "42"),
+            ("list", "Default::default()"),
+        ],
+    );
+}
+
+#[staged_builder]
+struct Callbacks {
+    pointer: fn(i32) -> i32,
+    #[builder(boxed_fn)]
+    boxed: Box<dyn Fn(i32) -> i32>,
+    #[builder(boxed_fn, default = std::sync::Arc::new(|_| {}))]
+    shared: std::sync::Arc<dyn Fn(&str) + Send + Sync>,
+}
+
+fn double(v: i32) -> i32 {
+    v * 2
+}
+
+#[test]
+fn boxed_fn() {
+    let offset = 10;
+    let callbacks = Callbacks::builder()
+        .pointer(double)
+        .boxed(move |v| v + offset)
+        .build();
+    assert_eq!((callbacks.pointer)(1), 2);
+    assert_eq!((callbacks.boxed)(1), 11);
+    (callbacks.shared)("hi");
+
+    let called = std::sync::Arc::new(std::sync::atomic::AtomicBool::new(false));
+    let callbacks = Callbacks::builder()
+        .pointer(double)
+        .boxed(|v| v)
+        .shared({
+            let called = called.clone();
+            move |s| {
+                assert_eq!(s, "hi");
+                called.store(true, std::sync::atomic::Ordering::SeqCst);
+            }
+        })
+        .build();
+    (callbacks.shared)("hi");
+    assert!(called.load(std::sync::atomic::Ordering::SeqCst));
+}
+
+#[staged_builder]
+#[builder(deref_view)]
+struct DerefView {
+    a: i32,
+    #[builder(default = 5)]
+    b: i32,
+    #[builder(list(item(type = u32)))]
+    c: Vec<u32>,
+}
+
+#[test]
+fn deref_view() {
+    let builder = DerefView::builder().a(1).push_c(2);
+    assert_eq!(builder.a, 1);
+    assert_eq!(builder.b, 5);
+    assert_eq!(builder.c, [2]);
+
+    let b = builder.a + builder.b;
+    let builder = builder.b(b);
+    let actual = builder.build();
+    assert_eq!((actual.a, actual.b, actual.c), (1, 6, vec![2]));
+}
+
+#[derive(PartialEq, Debug)]
+#[staged_builder]
+#[builder(unordered)]
+struct Unordered {
+    a: i32,
+    #[builder(into)]
+    b: String,
+    #[builder(default = 3)]
+    c: i32,
+    d: bool,
+}
+
+#[test]
+fn unordered() {
+    let actual = Unordered::builder()
+        .d(true)
+        .c(4)
+        .b("hello")
+        .a(1)
+        .build()
+        .unwrap();
+    let expected = Unordered {
+        a: 1,
+        b: "hello".to_string(),
+        c: 4,
+        d: true,
+    };
+    assert_eq!(actual, expected);
+
+    let err = Unordered::builder()
+        .d(false)
+        .b("hello")
+        .build()
+        .unwrap_err();
+    assert_eq!(err.fields().collect::<Vec<_>>(), ["a"]);
+    assert_eq!(err.to_string(), "missing required fields: `a`");
+
+    let err = Unordered::builder().c(1).build().unwrap_err();
+    assert_eq!(err.fields().collect::<Vec<_>>(), ["a", "b", "d"]);
+}
+
+#[staged_builder]
+struct IntoThen {
+    #[builder(into = String, then = std::sync::Arc::from)]
+    a: std::sync::Arc<str>,
+    #[builder(default, into = String, then = |s: String| s.into_boxed_str())]
+    b: Box<str>,
+}
+
+#[test]
+fn into_then() {
+    let actual = IntoThen::builder().a("hello").b("world").build();
+    assert_eq!(&*actual.a, "hello");
+    assert_eq!(&*actual.b, "world");
+}
+
+#[derive(PartialEq, Debug)]
+#[staged_builder]
+struct Generic<T, U: Ord> {
+    a: U,
+    #[builder(into)]
+    b: Vec<T>,
+    #[builder(list(item(type = T, into)))]
+    c: Vec<T>,
+    #[builder(set(item(type = U)))]
+    d: BTreeSet<U>,
+}
+
+#[test]
+fn generic() {
+    let actual = Generic::<String, i32>::builder()
+        .a(1)
+        .b(vec!["a".to_string()])
+        .push_c("b")
+        .extend_c(["c"])
+        .insert_d(2)
+        .build();
+    let expected = Generic {
+        a: 1,
+        b: vec!["a".to_string()],
+        c: vec!["b".to_string(), "c".to_string()],
+        d: BTreeSet::from([2]),
+    };
+    assert_eq!(actual, expected);
+}
+
+#[staged_builder]
+struct WhereClause<T>
+where
+    T: Default + Clone,
+{
+    a: T,
+    #[builder(default)]
+    b: T,
+}
+
+#[test]
+fn where_clause() {
+    let actual = WhereClause::builder().a("a".to_string()).build();
+    assert_eq!(actual.a, "a");
+    assert_eq!(actual.b, "");
+
+    let actual = WhereClause::builder().a(1).b(2).build();
+    assert_eq!(actual.a, 1);
+    assert_eq!(actual.b, 2);
+}
+
+#[staged_builder]
+#[builder(field_names)]
+#[allow(dead_code)]
+struct FieldNames {
+    a: i32,
+    #[builder(default)]
+    b: i32,
+    c: i32,
+    #[builder(list(item(type = i32)))]
+    d: Vec<i32>,
+}
+
+#[test]
+fn field_names() {
+    assert_eq!(FieldNames::FIELDS, ["a", "b", "c", "d"]);
+    assert_eq!(FieldNames::REQUIRED_FIELDS, ["a", "c"]);
+}
+
+#[derive(PartialEq, Debug)]
+#[staged_builder]
+#[builder(dynamic)]
+struct Dynamic {
+    a: i32,
+    b: String,
+    #[builder(default = 5)]
+    c: i32,
+}
+
+#[test]
+fn dynamic() {
+    let mut builder = dynamic::PartialBuilder::default();
+    builder.set_field_by_name("b", "hello".to_string()).unwrap();
+    builder.set_field_by_name("a", 1).unwrap();
+    builder.set_field_by_name("a", "wrong type").unwrap_err();
+    builder.set_field_by_name("d", 1).unwrap_err();
+    let actual = builder.finish().unwrap();
+    let expected = Dynamic {
+        a: 1,
+        b: "hello".to_string(),
+        c: 5,
+    };
+    assert_eq!(actual, expected);
+
+    let mut builder = dynamic::PartialBuilder::default();
+    builder.set_field_by_name("c", 2).unwrap();
+    let err = builder.finish().unwrap_err();
+    assert_eq!(err.fields().collect::<Vec<_>>(), ["a", "b"]);
+}
+
+#[staged_builder]
+struct NestedCollections {
+    #[builder(map(key(type = String, into), value(list(item(type = i32)))))]
+    a: HashMap<String, Vec<i32>>,
+    #[builder(list(item(set(item(type = i32)))))]
+    b: Vec<BTreeSet<i32>>,
+}
+
+#[test]
+fn nested_collections() {
+    let actual = NestedCollections::builder()
+        .insert_a("x", [1, 2])
+        .extend_a([("y".to_string(), vec![3])])
+        .push_b([2, 1, 2])
+        .build();
+    assert_eq!(
+        actual.a,
+        HashMap::from([("x".to_string(), vec![1, 2]), ("y".to_string(), vec![3])]),
+    );
+    assert_eq!(actual.b, [BTreeSet::from([1, 2])]);
+}
+
+macro_rules! builder_struct {
+    ($name:ident, $field:ident, $optional:ident, $default:expr) => {
+        #[derive(PartialEq, Debug)]
+        #[staged_builder]
+        struct $name {
+            $field: i32,
+            #[builder(default = $default)]
+            $optional: i32,
+            #[builder(into)]
+            literal: String,
+        }
+    };
+}
+
+builder_struct!(MacroGenerated, required, optional, 5);
+
+#[test]
+fn macro_generated() {
+    let actual = MacroGenerated::builder()
+        .required(1)
+        .literal("hi")
+        .optional(2)
+        .build();
+    let expected = MacroGenerated {
+        required: 1,
+        optional: 2,
+        literal: "hi".to_string(),
+    };
+    assert_eq!(actual, expected);
+
+    let builder: macro_generated::Builder<macro_generated::Complete> =
+        MacroGenerated::builder().required(1).literal("hi");
+    assert_eq!(builder.build().optional, 5);
+}
+
+#[staged_builder]
+struct CowField {
+    #[builder(into)]
+    a: Cow<'static, str>,
+    #[builder(default, into)]
+    b: Cow<'static, str>,
+}
+
+#[test]
+fn cow_field() {
+    let actual = CowField::builder()
+        .a("borrowed")
+        .b("owned".to_string())
+        .build();
+    assert!(matches!(actual.a, Cow::Borrowed("borrowed")));
+    assert!(matches!(actual.b, Cow::Owned(s) if s == "owned"));
+}
+
+#[staged_builder]
+struct BorrowedCow<'a> {
+    #[builder(into)]
+    a: Cow<'a, str>,
+    #[builder(default, into)]
+    b: Cow<'a, str>,
+}
+
+#[test]
+fn borrowed_cow() {
+    let borrowed = "borrowed".to_string();
+    let actual = BorrowedCow::builder()
+        .a(borrowed.as_str())
+        .b("owned".to_string())
+        .build();
+    assert!(matches!(actual.a, Cow::Borrowed("borrowed")));
+    assert!(matches!(actual.b, Cow::Owned(s) if s == "owned"));
+
+    let actual = BorrowedCow::builder().a(&borrowed[..3]).build();
+    assert!(matches!(actual.a, Cow::Borrowed("bor")));
+    assert_eq!(actual.b, "");
+}
+
+#[staged_builder]
+struct View<'a> {
+    data: &'a [u8],
+    #[builder(default)]
+    name: Option<&'a str>,
+}
+
+#[test]
+fn borrowed_view() {
+    let data = vec![1, 2, 3];
+    let name = "view".to_string();
+    let view = View::builder().data(&data).name(Some(&name)).build();
+    assert_eq!(view.data, [1, 2, 3]);
+    assert_eq!(view.name, Some("view"));
+}
+
+#[staged_builder]
+struct LateLifetime<'a> {
+    id: u32,
+    name: &'a str,
+    #[builder(default)]
+    bytes: &'a [u8],
+}
+
+#[test]
+fn late_lifetime() {
+    let name = "late".to_string();
+    let bytes = vec![1, 2];
+    let actual = LateLifetime::builder()
+        .id(1)
+        .name(&name)
+        .bytes(&bytes)
+        .build();
+    assert_eq!(actual.id, 1);
+    assert_eq!(actual.name, "late");
+    assert_eq!(actual.bytes, [1, 2]);
+
+    let actual = LateLifetime::builder().id(2).name(&name).build();
+    assert_eq!(actual.bytes, []);
+}
+
+#[staged_builder]
+#[builder(by_ref)]
+struct ByRef {
+    a: i32,
+    #[builder(list(item(type = i32)))]
+    b: Vec<i32>,
+    #[builder(default)]
+    c: i32,
+}
+
+#[test]
+fn by_ref_configure() {
+    let actual = ByRef::builder()
+        .a(1)
+        .configure(|b| {
+            for i in 0..3 {
+                b.push_b(i);
+            }
+            b.c(4);
+        })
+        .build();
+    assert_eq!((actual.a, actual.b, actual.c), (1, vec![0, 1, 2], 4));
+}
+
+#[test]
+fn by_ref_loop() {
+    let mut builder = ByRef::builder().a(1);
+    for i in 0..3 {
+        builder.push_b(i).c(i);
+    }
+    let actual = builder.build();
+    assert_eq!((actual.a, actual.b, actual.c), (1, vec![0, 1, 2], 2));
+}
+
+#[staged_builder]
+struct Exact {
+    #[builder(into, exact)]
+    a: String,
+    #[builder(default, into, exact)]
+    b: String,
+}
+
+#[test]
+fn exact() {
+    let actual = Exact::builder().a("a").b_exact("b".to_string()).build();
+    assert_eq!((actual.a.as_str(), actual.b.as_str()), ("a", "b"));
+
+    let actual = Exact::builder().a_exact("a".to_string()).b("b").build();
+    assert_eq!((actual.a.as_str(), actual.b.as_str()), ("a", "b"));
+}
+
+#[derive(PartialEq, Debug)]
+#[staged_builder]
+#[builder(default_all_optional_via = Default)]
+struct DefaultOptions {
+    a: i32,
+    b: Option<i32>,
+    c: Option<String>,
+    #[builder(into)]
+    d: Option<String>,
+    e: std::option::Option<bool>,
+}
+
+#[test]
+fn default_all_optional_via() {
+    let actual = DefaultOptions::builder().a(1).b(Some(2)).build();
+    let expected = DefaultOptions {
+        a: 1,
+        b: Some(2),
+        c: None,
+        d: None,
+        e: None,
+    };
+    assert_eq!(actual, expected);
+}
+
+#[staged_builder]
+struct StringField {
+    #[builder(string)]
+    a: String,
+    #[builder(string(push(type = &str)))]
+    b: String,
+}
+
+#[test]
+fn string_field() {
+    let actual = StringField::builder()
+        .push_a("x = ")
+        .push_a(1)
+        .push_a(',')
+        .push_a(2.5)
+        .b("replaced")
+        .push_b("!")
+        .build();
+    assert_eq!(actual.a, "x = 1,2.5");
+    assert_eq!(actual.b, "replaced!");
+}
+
+#[staged_builder]
+struct ArrayField {
+    #[builder(array(len = 3, item(type = u8)))]
+    rgb: [u8; 3],
+    #[builder(default, array(len = 2, item(type = String, into)))]
+    names: [String; 2],
+}
+
+#[test]
+fn array_field() {
+    let actual = ArrayField::builder().rgb(1, 2, 3).build();
+    assert_eq!(actual.rgb, [1, 2, 3]);
+    assert_eq!(actual.names, ["", ""]);
+
+    let actual = ArrayField::builder().rgb(4, 5, 6).names("a", "b").build();
+    assert_eq!(actual.rgb, [4, 5, 6]);
+    assert_eq!(actual.names, ["a", "b"]);
+}
+
+#[staged_builder]
+#[builder(validate = |s: &ValidateClosure| -> Result<(), &'static str> {
+    if s.a > s.b {
+        Err("a > b")
+    } else {
+        Ok(())
+    }
+})]
+struct ValidateClosure {
+    a: u32,
+    b: u32,
+}
+
+#[test]
+fn validate_closure() {
+    let actual = ValidateClosure::builder().a(1).b(2).build().unwrap();
+    assert_eq!(actual.a, 1);
+    assert_eq!(actual.b, 2);
+
+    let err = ValidateClosure::builder().a(2).b(1).build().err().unwrap();
+    assert_eq!(err, "a > b");
+}
+
+#[derive(Debug, PartialEq)]
+struct TryIntoError(TryFromIntError);
+
+impl From<TryFromIntError> for TryIntoError {
+    fn from(e: TryFromIntError) -> Self {
+        TryIntoError(e)
+    }
+}
+
+#[staged_builder]
+#[builder(error = TryIntoError)]
+struct TryIntoField {
+    #[builder(try_into)]
+    a: u8,
+    #[builder(default, try_into)]
+    b: u16,
+}
+
+#[test]
+fn try_into_field() {
+    let actual = TryIntoField::builder().a(1u32).b(2i64).build().unwrap();
+    assert_eq!(actual.a, 1);
+    assert_eq!(actual.b, 2);
+
+    let actual = TryIntoField::builder().a(3i32).build().unwrap();
+    assert_eq!(actual.a, 3);
+    assert_eq!(actual.b, 0);
+
+    let err = TryIntoField::builder().a(256).build().err().unwrap();
+    assert_eq!(err, TryIntoError(u8::try_from(256).unwrap_err()));
+
+    assert!(TryIntoField::builder().a(1).b(-1).build().is_err());
+}
+
+struct NotDebug;
+
+#[staged_builder]
+#[builder(debug)]
+struct DebugBuilder {
+    a: u32,
+    b: NotDebug,
+    #[builder(default)]
+    c: String,
+}
+
+#[test]
+fn debug_builder() {
+    let builder = DebugBuilder::builder().a(1);
+    assert_eq!(format!("{:?}", builder), "Builder(BStage { a: 1 })");
+    let DebugBuilder { a, b: NotDebug, c } = builder.b(NotDebug).build();
+    assert_eq!(a, 1);
+    assert_eq!(c, "");
+}
+
+#[derive(Debug, PartialEq)]
+#[staged_builder]
+#[builder(clone)]
+struct CloneBuilder {
+    a: u32,
+    #[builder(into)]
+    b: String,
+    #[builder(default)]
+    c: bool,
+}
+
+#[test]
+fn clone_builder() {
+    let builder = CloneBuilder::builder().a(1);
+    let first = builder.clone().b("first").build();
+    let second = builder.b("second").c(true).build();
+    assert_eq!(
+        first,
+        CloneBuilder {
+            a: 1,
+            b: "first".to_string(),
+            c: false,
+        }
+    );
+    assert_eq!(
+        second,
+        CloneBuilder {
+            a: 1,
+            b: "second".to_string(),
+            c: true,
+        }
+    );
+}
+
+#[derive(Debug, PartialEq)]
+#[staged_builder]
+struct SkipField {
+    a: u32,
+    #[builder(skip, default = vec![1, 2])]
+    cache: Vec<u32>,
+    #[builder(default)]
+    b: bool,
+}
+
+#[test]
+fn skip_field() {
+    let actual = SkipField::builder().a(1).b(true).build();
+    let expected = SkipField {
+        a: 1,
+        cache: vec![1, 2],
+        b: true,
+    };
+    assert_eq!(actual, expected);
+}
+
+#[staged_builder]
+struct OptionField {
+    #[builder(option(item(type = i32)))]
+    a: Option<i32>,
+    #[builder(option(item(type = String, into)))]
+    b: Option<String>,
+    #[builder(option(item(type = Option<u32>)))]
+    c: Option<Option<u32>>,
+}
+
+#[test]
+fn option_field() {
+    let actual = OptionField::builder().build();
+    assert_eq!(actual.a, None);
+    assert_eq!(actual.b, None);
+    assert_eq!(actual.c, None);
+
+    let actual = OptionField::builder().a(1).b("b").c(None).build();
+    assert_eq!(actual.a, Some(1));
+    assert_eq!(actual.b.as_deref(), Some("b"));
+    assert_eq!(actual.c, Some(None));
+
+    let actual = OptionField::builder()
+        .a(1)
+        .unset_a()
+        .c(Some(2))
+        .unset_c()
+        .build();
+    assert_eq!(actual.a, None);
+    assert_eq!(actual.c, None);
+}
+
+#[staged_builder]
+#[builder(field_names)]
+struct CfgField {
+    a: u32,
+    #[cfg(any())]
+    b: u32,
+    #[cfg(feature = "tracing")]
+    #[builder(default)]
+    c: bool,
+    #[cfg(any())]
+    #[builder(default)]
+    d: u32,
+}
+
+#[test]
+fn cfg_field() {
+    let actual = CfgField::builder().a(1).build();
+    assert_eq!(actual.a, 1);
+
+    #[cfg(feature = "tracing")]
+    {
+        let actual = CfgField::builder().a(1).c(true).build();
+        assert!(actual.c);
+        assert_eq!(CfgField::FIELDS, ["a", "c"]);
+    }
+    #[cfg(not(feature = "tracing"))]
+    assert_eq!(CfgField::FIELDS, ["a"]);
+}
+
+#[derive(Debug, PartialEq)]
+#[staged_builder]
+struct SharedStage {
+    #[builder(stage = Coords)]
+    x: i32,
+    #[builder(default)]
+    label: String,
+    #[builder(stage = Coords, into)]
+    y: i64,
+    z: i32,
+}
+
+#[test]
+fn shared_stage() {
+    let actual = SharedStage::builder().coords(1, 2i32).z(3).build();
+    let expected = SharedStage {
+        x: 1,
+        label: String::new(),
+        y: 2,
+        z: 3,
+    };
+    assert_eq!(actual, expected);
+}
+
+#[staged_builder]
+#[builder(getters)]
+struct Getters {
+    width: u32,
+    height: u32,
+    #[builder(default)]
+    label: String,
+}
+
+#[test]
+fn getters() {
+    let builder = Getters::builder().width(2);
+    let height = *builder.get_width() * 3;
+    let builder = builder.height(height);
+    assert_eq!(*builder.get_height(), 6);
+    assert_eq!(builder.get_label(), "");
+
+    let actual = builder.label("rect".to_string()).build();
+    assert_eq!(actual.width, 2);
+    assert_eq!(actual.height, 6);
+    assert_eq!(actual.label, "rect");
+}
+
+#[derive(PartialEq, Debug)]
+#[staged_builder]
+#[non_exhaustive]
+pub struct NonExhaustive {
+    pub a: i32,
+    #[builder(default)]
+    pub b: bool,
+}
+
+#[test]
+fn non_exhaustive() {
+    let actual = NonExhaustive::builder().a(1).build();
+    assert_eq!(actual, NonExhaustive { a: 1, b: false });
+}
+
+#[staged_builder]
+#[builder(setter_prefix = "with_")]
+struct SetterPrefix {
+    r#type: u32,
+    #[builder(into, exact)]
+    build: String,
+    #[builder(list(item(type = u32)))]
+    values: Vec<u32>,
+}
+
+#[test]
+fn setter_prefix() {
+    let actual = SetterPrefix::builder()
+        .with_type(1)
+        .with_build_exact("b".to_string())
+        .push_values(2)
+        .with_values([3])
+        .build();
+    assert_eq!(actual.r#type, 1);
+    assert_eq!(actual.build, "b");
+    assert_eq!(actual.values, [3]);
+}
+
+#[staged_builder]
+#[builder(setter_prefix = "with_")]
+struct RenamedSetters {
+    #[builder(name = id)]
+    identifier: u32,
+    #[builder(name = tag, list(item(type = String, into)))]
+    raw_tags: Vec<String>,
+    #[builder(default)]
+    count: u32,
+}
+
+#[test]
+fn renamed_setters() {
+    let actual = RenamedSetters::builder()
+        .id(1)
+        .push_tag("a")
+        .extend_tag(["b"])
+        .with_count(2)
+        .build();
+    assert_eq!(actual.identifier, 1);
+    assert_eq!(actual.raw_tags, ["a", "b"]);
+    assert_eq!(actual.count, 2);
+
+    let _: renamed_setters::Builder<renamed_setters::IdentifierStage> = RenamedSetters::builder();
+}
+
+#[test]
+fn build_with() {
+    let actual = Update::builder().a(1).b("hello").build_with(|v| {
+        assert_eq!(v.a, 1);
+        v.a += 1;
+    });
+    assert_eq!(
+        actual,
+        Update {
+            a: 2,
+            b: "hello".to_string(),
+        }
+    );
+
+    Validated::builder()
+        .even(1)
+        .build_with(|v| v.even += 1)
+        .unwrap();
+    Validated::builder()
+        .even(0)
+        .build_with(|v| v.even += 1)
+        .err()
+        .unwrap();
+}
+
+#[derive(Debug, PartialEq)]
+struct ParseError(ParseIntError);
+
+impl From<ParseIntError> for ParseError {
+    fn from(e: ParseIntError) -> Self {
+        ParseError(e)
+    }
+}
+
+#[staged_builder]
+#[builder(error = ParseError)]
+struct FallibleCustom {
+    #[builder(custom(type = &str, convert = str::parse, fallible))]
+    a: i32,
+}
+
+#[test]
+fn fallible_custom() {
+    let actual = FallibleCustom::builder().a("12").build().unwrap();
+    assert_eq!(actual.a, 12);
+
+    let err = FallibleCustom::builder().a("x").build().err().unwrap();
+    assert_eq!(err, ParseError("x".parse::<i32>().unwrap_err()));
+}
+
+#[derive(Debug, PartialEq)]
+#[staged_builder]
+struct TupleStruct(u32, #[builder(into)] String, #[builder(default)] bool);
+
+#[test]
+fn tuple_struct() {
+    let actual = TupleStruct::builder().field_0(1).field_1("a").build();
+    assert_eq!(actual, TupleStruct(1, "a".to_string(), false));
+
+    let builder: tuple_struct::Builder<tuple_struct::Field1Stage> =
+        TupleStruct::builder().field_0(2);
+    let actual = builder.field_1("b").field_2(true).build();
+    assert_eq!(actual, TupleStruct(2, "b".to_string(), true));
+}
+
+#[staged_builder]
+#[builder(update)]
+struct ResetFields {
+    #[builder(default = 42)]
+    a: u32,
+    #[builder(list(item(type = u32)))]
+    b: Vec<u32>,
+}
+
+#[test]
+fn reset_fields() {
+    let actual = ResetFields::builder()
+        .a(1)
+        .push_b(2)
+        .reset_a()
+        .reset_b()
+        .build();
+    assert_eq!(actual.a, 42);
+    assert_eq!(actual.b, Vec::<u32>::new());
+
+    let actual = reset_fields::Builder::from(actual).a(3).reset_a().build();
+    assert_eq!(actual.a, 42);
+}
+
+type Names = BTreeSet<String>;
+
+#[staged_builder]
+struct OrderedCollections {
+    #[builder(set(type = Names, item(type = String, into)))]
+    names: Names,
+    #[builder(map(type = BTreeMap<u32, u32>, key(type = u32), value(type = u32)))]
+    scores: BTreeMap<u32, u32>,
+}
+
+#[test]
+fn ordered_collections() {
+    let actual = OrderedCollections::builder()
+        .names(["b", "c", "a"])
+        .scores([(3, 30), (1, 10)])
+        .insert_scores(2, 20)
+        .build();
+    assert_eq!(actual.names.iter().collect::<Vec<_>>(), ["a", "b", "c"]);
+    assert_eq!(
+        actual.scores.into_iter().collect::<Vec<_>>(),
+        [(1, 10), (2, 20), (3, 30)]
+    );
+}
+
+#[staged_builder]
+struct DequeField {
+    #[builder(deque(item(type = i32)))]
+    values: VecDeque<i32>,
+}
+
+#[test]
+fn deque_field() {
+    let actual = DequeField::builder()
+        .push_back_values(2)
+        .push_front_values(1)
+        .push_back_values(3)
+        .extend_values([4, 5])
+        .build();
+    assert_eq!(actual.values, [1, 2, 3, 4, 5]);
+}
+
+#[staged_builder]
+struct CollectionCapacity {
+    #[builder(list(item(type = u32), capacity = 128))]
+    values: Vec<u32>,
+    #[builder(map(key(type = u32), value(type = u32), capacity = 16))]
+    entries: HashMap<u32, u32>,
+}
+
+#[test]
+fn collection_capacity() {
+    let actual = CollectionCapacity::builder().push_values(1).build();
+    assert_eq!(actual.values, [1]);
+    assert!(actual.values.capacity() >= 128);
+    assert!(actual.entries.capacity() >= 16);
+}
+
+#[derive(Debug, PartialEq)]
+#[staged_builder]
+#[builder(default_struct)]
+struct DefaultStruct {
+    #[builder(default = 8080)]
+    port: u16,
+    #[builder(default, into)]
+    host: String,
+    #[builder(list(item(type = String)))]
+    tags: Vec<String>,
+}
+
+#[test]
+fn default_struct() {
+    assert_eq!(
+        DefaultStruct::default(),
+        DefaultStruct {
+            port: 8080,
+            host: String::new(),
+            tags: vec![],
+        }
+    );
+}
+
+#[derive(Debug, PartialEq)]
+#[staged_builder]
+enum Source {
+    File {
+        #[builder(into)]
+        path: String,
+        #[builder(default)]
+        follow_symlinks: bool,
+    },
+    Network {
+        port: u16,
+        #[builder(default = 30)]
+        timeout: u32,
+    },
+    Stdin,
+}
+
+#[test]
+fn enum_variants() {
+    let actual = Source::file_builder().path("/tmp/a").build();
+    assert_eq!(
+        actual,
+        Source::File {
+            path: "/tmp/a".to_string(),
+            follow_symlinks: false,
+        }
+    );
+
+    let actual = Source::network_builder().port(80).timeout(5).build();
+    assert_eq!(
+        actual,
+        Source::Network {
+            port: 80,
+            timeout: 5
+        }
+    );
+
+    let builder: source_network::Builder<source_network::Complete> =
+        Source::network_builder().port(8080);
+    assert_eq!(
+        builder.build(),
+        Source::Network {
+            port: 8080,
+            timeout: 30,
+        }
+    );
+    assert_ne!(Source::Stdin, Source::network_builder().port(1).build());
+}
+
+#[derive(Debug, PartialEq)]
+#[staged_builder]
+#[builder(validate = check, error = String)]
+struct ValidateMethod {
+    min: u32,
+    max: u32,
+}
+
+impl ValidateMethod {
+    fn check(&self) -> Result<(), String> {
+        if self.min <= self.max {
+            Ok(())
+        } else {
+            Err(format!("{} > {}", self.min, self.max))
+        }
+    }
+}
+
+mod checks {
+    pub fn positive(value: &super::ValidateFunction) -> Result<(), &'static str> {
+        if value.a > 0 {
+            Ok(())
+        } else {
+            Err("not positive")
+        }
+    }
+}
+
+#[derive(Debug, PartialEq)]
+#[staged_builder]
+#[builder(validate = checks::positive, error = String)]
+struct ValidateFunction {
+    a: i32,
+}
+
+#[test]
+fn validate_method() {
+    let actual = ValidateMethod::builder().min(1).max(2).build();
+    assert_eq!(actual, Ok(ValidateMethod { min: 1, max: 2 }));
+
+    let actual = ValidateMethod::builder().min(3).max(2).build();
+    assert_eq!(actual, Err("3 > 2".to_string()));
+
+    let actual = ValidateFunction::builder().a(1).build();
+    assert_eq!(actual, Ok(ValidateFunction { a: 1 }));
+
+    let actual = ValidateFunction::builder().a(0).build();
+    assert_eq!(actual, Err("not positive".to_string()));
+}
+
+#[staged_builder]
+struct RequiredFieldCount {
+    a: u32,
+    b: u32,
+    #[builder(default)]
+    c: u32,
+}
+
+#[test]
+fn required_field_count() {
+    fn required_fields<T>(_: &required_field_count::Builder<T>) -> usize {
+        required_field_count::Builder::<T>::REQUIRED_FIELD_COUNT
+    }
+
+    let builder = RequiredFieldCount::builder();
+    assert_eq!(required_fields(&builder), 2);
+
+    let actual = builder.a(1).b(2).build();
+    assert_eq!((actual.a, actual.b, actual.c), (1, 2, 0));
+}
+
+#[staged_builder]
+struct NamedArgCustom {
+    #[builder(custom(type = &str, arg = s, convert = s.to_owned()))]
+    name: String,
+    #[builder(custom(type = impl Display, arg = s, convert = s.to_string()))]
+    label: String,
+    #[builder(list(item(custom(type = &'static str, arg = s, convert = s.len()))))]
+    lengths: Vec<usize>,
+    #[builder(default, custom(type = impl Display, arg = s, convert = Some(s.to_string())))]
+    note: Option<String>,
+}
+
+#[test]
+fn named_arg_custom() {
+    let actual = NamedArgCustom::builder()
+        .name("foo")
+        .label(12)
+        .push_lengths("ab")
+        .extend_lengths(["abc"])
+        .note('x')
+        .build();
+    assert_eq!(actual.name, "foo");
+    assert_eq!(actual.label, "12");
+    assert_eq!(actual.lengths, [2, 3]);
+    assert_eq!(actual.note.as_deref(), Some("x"));
+}
+
+#[staged_builder]
+#[builder(constructor = new_builder)]
+struct CustomConstructor {
+    a: u32,
+}
+
+impl CustomConstructor {
+    fn builder() -> &'static str {
+        "not a staged builder"
+    }
+}
+
+#[test]
+fn custom_constructor() {
+    let actual = CustomConstructor::new_builder().a(1).build();
+    assert_eq!(actual.a, 1);
+    assert_eq!(CustomConstructor::builder(), "not a staged builder");
+}
+
+pub mod restricted_stages {
+    use staged_builder::staged_builder;
+
+    #[staged_builder]
+    #[builder(stage_vis = pub(crate))]
+    pub struct Public {
+        pub a: u32,
+    }
+}
+
+#[test]
+fn restricted_stages() {
+    let builder: restricted_stages::public::Builder<restricted_stages::public::AStage> =
+        restricted_stages::Public::builder();
+    assert_eq!(builder.a(1).build().a, 1);
+}
+
+#[staged_builder]
+#[builder(eq)]
+struct EqBuilder {
+    a: u32,
+    b: u32,
+    #[builder(default)]
+    c: u32,
+}
+
+#[test]
+fn eq_builder() {
+    assert!(EqBuilder::builder() == EqBuilder::builder());
+    assert!(EqBuilder::builder().a(1) == EqBuilder::builder().a(1));
+    assert!(EqBuilder::builder().a(1) != EqBuilder::builder().a(2));
+
+    let complete = EqBuilder::builder().a(1).b(2);
+    assert!(complete == EqBuilder::builder().a(1).b(2).c(0));
+    assert!(complete != EqBuilder::builder().a(1).b(2).c(3));
+
+    let actual = complete.build();
+    assert_eq!((actual.a, actual.b, actual.c), (1, 2, 0));
+}
+
+#[staged_builder]
+struct SelfDefault {
+    #[builder(default = Self::DEFAULT_PORT)]
+    port: u16,
+    #[builder(default = Self::default_hosts())]
+    hosts: Vec<String>,
+    #[builder(default())]
+    verbose: bool,
+}
+
+impl SelfDefault {
+    const DEFAULT_PORT: u16 = 8080;
+
+    fn default_hosts() -> Vec<String> {
+        vec!["localhost".to_string()]
+    }
+}
+
+#[test]
+fn self_default() {
+    let actual = SelfDefault::builder().build();
+    assert_eq!(actual.port, 8080);
+    assert_eq!(actual.hosts, ["localhost"]);
+    assert!(!actual.verbose);
+}
+
+#[staged_builder]
+struct DefaultCall {
+    #[builder(default = String::default())]
+    name: String,
+    #[builder(into, default = Vec::default())]
+    values: Vec<u32>,
+}
+
+#[test]
+fn default_call() {
+    let actual = DefaultCall::builder().build();
+    assert_eq!(actual.name, "");
+    assert!(actual.values.is_empty());
+}
+
+#[staged_builder]
+struct IntoFrom {
+    #[builder(into = String)]
+    a: std::sync::Arc<str>,
+}
+
+#[test]
+fn into_from() {
+    let actual = IntoFrom::builder().a("hello").build();
+    assert_eq!(&*actual.a, "hello");
+}
+
+#[staged_builder]
+struct ClonedItems {
+    #[builder(list(item(type = u32, cloned)))]
+    values: Vec<u32>,
+    #[builder(set(item(type = String, cloned)))]
+    names: BTreeSet<String>,
+}
+
+#[test]
+fn cloned_items() {
+    let values: &[u32] = &[1, 2];
+    let names = vec!["b".to_string(), "a".to_string()];
+    let actual = ClonedItems::builder()
+        .values(values)
+        .extend_values(values)
+        .push_values(3)
+        .names(&names)
+        .insert_names("c".to_string())
+        .build();
+    assert_eq!(actual.values, [1, 2, 1, 2, 3]);
+    assert_eq!(actual.names.iter().collect::<Vec<_>>(), ["a", "b", "c"]);
+}
+
+#[derive(Debug, PartialEq, StagedBuilder)]
+#[builder(mod = derived_builder)]
+struct Derived {
+    #[builder(into)]
+    a: String,
+    #[builder(default = 1)]
+    b: u32,
+}
+
+#[derive(Debug, PartialEq)]
+#[staged_builder]
+#[builder(mod = attribute_builder)]
+struct Attribute {
+    #[builder(into)]
+    a: String,
+    #[builder(default = 1)]
+    b: u32,
+}
+
+#[test]
+fn derive_and_attribute() {
+    let derived: derived_builder::Builder<derived_builder::Complete> = Derived::builder().a("x");
+    let attribute: attribute_builder::Builder<attribute_builder::Complete> =
+        Attribute::builder().a("x");
+
+    let derived = derived.b(2).build();
+    let attribute = attribute.b(2).build();
+    assert_eq!(
+        derived,
+        Derived {
+            a: "x".to_string(),
+            b: 2
+        }
+    );
+    assert_eq!(
+        attribute,
+        Attribute {
+            a: "x".to_string(),
+            b: 2
+        }
+    );
+}
+
+#[staged_builder]
+struct WholeInto {
+    #[builder(list(item(type = String, into), whole_into))]
+    values: Vec<String>,
+}
+
+#[test]
+fn whole_into() {
+    let values = vec!["a".to_string(), "b".to_string()];
+    let actual = WholeInto::builder()
+        .push_values("x")
+        .set_values(values)
+        .build();
+    assert_eq!(actual.values, ["a", "b"]);
+
+    let actual = WholeInto::builder()
+        .set_values(["c".to_string()])
+        .push_values("d")
+        .build();
+    assert_eq!(actual.values, ["c", "d"]);
+}
+
+mod reexported {
+    use staged_builder::staged_builder;
+
+    #[staged_builder]
+    #[builder(reexport)]
+    pub struct Reexported {
+        pub a: u32,
+        pub b: u32,
+    }
+}
+
+#[test]
+fn reexport() {
+    let builder: reexported::Builder<reexported::BStage> = reexported::Reexported::builder().a(1);
+    let builder: reexported::Builder<reexported::Complete> = builder.b(2);
+    let actual = builder.build();
+    assert_eq!((actual.a, actual.b), (1, 2));
+}
+
+static LAZY_DEFAULT_CALLS: AtomicUsize = AtomicUsize::new(0);
+
+fn expensive_default() -> Vec<u32> {
+    LAZY_DEFAULT_CALLS.fetch_add(1, Ordering::SeqCst);
+    vec![1, 2, 3]
+}
+
+#[staged_builder]
+struct LazyDefault {
+    #[builder(default = lazy(expensive_default()))]
+    values: Vec<u32>,
+}
+
+#[test]
+fn lazy_default() {
+    let actual = LazyDefault::builder().values(vec![4]).build();
+    assert_eq!(actual.values, [4]);
+    assert_eq!(LAZY_DEFAULT_CALLS.load(Ordering::SeqCst), 0);
+
+    let actual = LazyDefault::builder().build();
+    assert_eq!(actual.values, [1, 2, 3]);
+    assert_eq!(LAZY_DEFAULT_CALLS.load(Ordering::SeqCst), 1);
+}
+
+mod lazy_shadowing {
+    use staged_builder::{staged_builder, Validate};
+
+    pub fn f() -> u32 {
+        1
+    }
+
+    pub fn value() -> u32 {
+        2
+    }
+
+    #[staged_builder]
+    #[builder(validate)]
+    pub struct LazyShadowing {
+        #[builder(default = lazy(f()))]
+        pub a: u32,
+        #[builder(default = lazy(value()))]
+        pub b: u32,
+    }
+
+    impl Validate for LazyShadowing {
+        type Error = &'static str;
+
+        fn validate(&self) -> Result<(), Self::Error> {
+            Ok(())
+        }
+    }
+}
+
+#[test]
+fn lazy_shadowing() {
+    let actual = lazy_shadowing::LazyShadowing::builder()
+        .build_with(|v| v.b += 1)
+        .unwrap();
+    assert_eq!((actual.a, actual.b), (1, 3));
+
+    let actual = lazy_shadowing::LazyShadowing::builder()
+        .map_a(|a| a + 10)
+        .build()
+        .unwrap();
+    assert_eq!((actual.a, actual.b), (11, 2));
+}
+
+#[staged_builder]
+struct DefaultInto {
+    #[builder(default = Cow::Borrowed("fallback"), into)]
+    label: Cow<'static, str>,
+}
+
+#[test]
+fn default_into() {
+    let actual = DefaultInto::builder().build();
+    assert!(matches!(actual.label, Cow::Borrowed("fallback")));
+
+    let actual = DefaultInto::builder().label("set").build();
+    assert_eq!(actual.label, "set");
+
+    let actual = DefaultInto::builder().label("set".to_string()).build();
+    assert!(matches!(actual.label, Cow::Owned(_)));
+}
+
+mod type_path {
+    pub mod nested {
+        use staged_builder::staged_builder;
+
+        #[staged_builder]
+        #[builder(mod = generated, type_path = crate::type_path::nested::TypePath)]
+        pub struct TypePath<T> {
+            pub value: T,
+        }
+    }
+}
+
+#[test]
+fn type_path() {
+    let actual = type_path::nested::TypePath::builder().value(1).build();
+    assert_eq!(actual.value, 1);
+
+    let builder: type_path::nested::generated::Builder<_> =
+        type_path::nested::TypePath::builder().value("a");
+    assert_eq!(builder.build().value, "a");
+}
+
+#[staged_builder]
+struct BuildInto {
+    a: u32,
+}
+
+struct BuildIntoWrapper(BuildInto);
+
+impl From<BuildInto> for BuildIntoWrapper {
+    fn from(v: BuildInto) -> Self {
+        BuildIntoWrapper(v)
+    }
+}
+
+#[staged_builder]
+#[builder(validate = |v: &BuildIntoValidated| -> Result<(), String> {
+    if v.a == 0 {
+        Err("zero".to_string())
+    } else {
+        Ok(())
+    }
+})]
+struct BuildIntoValidated {
+    a: u32,
+}
+
+#[test]
+fn build_into() {
+    let wrapper: BuildIntoWrapper = BuildInto::builder().a(1).build_into();
+    assert_eq!(wrapper.0.a, 1);
+
+    let actual = BuildIntoValidated::builder().a(2).build_into::<Box<_>>();
+    assert_eq!(actual.unwrap().a, 2);
+    let actual = BuildIntoValidated::builder().a(0).build_into::<Box<_>>();
+    assert_eq!(actual.err().unwrap(), "zero");
+}
+
+#[staged_builder]
+struct DefaultFrom {
+    #[builder(default_from = name)]
+    display_name: String,
+    #[builder(into)]
+    name: String,
+}
+
+#[test]
+fn default_from() {
+    let actual = DefaultFrom::builder().name("alice").build();
+    assert_eq!(actual.display_name, "alice");
+    assert_eq!(actual.name, "alice");
+
+    let actual = DefaultFrom::builder()
+        .name("bob")
+        .display_name("Bob".to_string())
+        .build();
+    assert_eq!(actual.display_name, "Bob");
+    assert_eq!(actual.name, "bob");
+}
+
+#[staged_builder]
+#[derive(Debug, PartialEq)]
+#[builder(flatten)]
+struct FlattenInner {
+    #[builder(default = 30)]
+    timeout: u32,
+    #[builder(default)]
+    retries: u32,
+}
+
+#[staged_builder]
+struct FlattenOuter {
+    name: &'static str,
+    #[builder(flatten)]
+    inner: FlattenInner,
+}
+
+#[test]
+fn flatten() {
+    let actual = FlattenOuter::builder().name("a").build();
+    assert_eq!(
+        actual.inner,
+        FlattenInner {
+            timeout: 30,
+            retries: 0
+        }
+    );
+
+    let actual = FlattenOuter::builder()
+        .name("a")
+        .inner(|b| b.timeout(5))
+        .inner(|b| b.retries(2))
+        .build();
+    assert_eq!(actual.name, "a");
+    assert_eq!(
+        actual.inner,
+        FlattenInner {
+            timeout: 5,
+            retries: 2
+        }
+    );
+}
+
+#[staged_builder]
+#[builder(strip_option)]
+struct StripOption {
+    required: u32,
+    nickname: Option<String>,
+    age: std::option::Option<u32>,
+    #[builder(into)]
+    explicit: Option<u32>,
+}
+
+#[test]
+fn strip_option() {
+    let actual = StripOption::builder().required(1).explicit(None).build();
+    assert_eq!(actual.required, 1);
+    assert_eq!(actual.nickname, None);
+    assert_eq!(actual.age, None);
+    assert_eq!(actual.explicit, None);
+
+    let actual = StripOption::builder()
+        .required(1)
+        .explicit(2)
+        .nickname("a".to_string())
+        .age(3)
+        .unset_age()
+        .build();
+    assert_eq!(actual.nickname.as_deref(), Some("a"));
+    assert_eq!(actual.age, None);
+    assert_eq!(actual.explicit, Some(2));
+}
+
+#[staged_builder]
+#[builder(conditional_setters)]
+struct ConditionalSetters {
+    required: u32,
+    #[builder(default = 5)]
+    optional: u32,
+    #[builder(default, into)]
+    label: String,
+}
+
+#[test]
+fn conditional_setters() {
+    let actual = ConditionalSetters::builder()
+        .required(1)
+        .optional_if(false, 10)
+        .label_if(true, "a")
+        .build();
+    assert_eq!(actual.required, 1);
+    assert_eq!(actual.optional, 5);
+    assert_eq!(actual.label, "a");
+
+    let actual = ConditionalSetters::builder()
+        .required(1)
+        .optional_if(true, 10)
+        .label_if(false, "a")
+        .build();
+    assert_eq!(actual.optional, 10);
+    assert_eq!(actual.label, "");
+}
+
+#[staged_builder]
+#[builder(field_names, debug)]
+struct RawIdents {
+    r#type: u32,
+    r#match: &'static str,
+    #[builder(default)]
+    r#async: bool,
+}
+
+#[test]
+fn raw_idents() {
+    let stage: raw_idents::Builder<raw_idents::MatchStage> = RawIdents::builder().r#type(1);
+    let actual = stage.r#match("a").r#async(true).build();
+    assert_eq!(actual.r#type, 1);
+    assert_eq!(actual.r#match, "a");
+    assert!(actual.r#async);
+
+    assert_eq!(RawIdents::FIELDS, ["type", "match", "async"]);
+    assert_eq!(
+        format!("{:?}", RawIdents::builder().r#type(1)),
+        "Builder(MatchStage { type: 1 })"
+    );
+}
+
+#[staged_builder]
+#[builder(into)]
+struct StructInto {
+    name: String,
+    path: std::path::PathBuf,
+    #[builder(into = false)]
+    exact: Cow<'static, str>,
+    #[builder(list(item(type = u32)))]
+    values: Vec<u32>,
+}
+
+#[test]
+fn struct_into() {
+    let actual = StructInto::builder()
+        .name("a")
+        .path("/tmp")
+        .exact(Cow::Borrowed("b"))
+        .push_values(1)
+        .build();
+    assert_eq!(actual.name, "a");
+    assert_eq!(actual.path, std::path::Path::new("/tmp"));
+    assert_eq!(actual.exact, "b");
+    assert_eq!(actual.values, [1]);
+}
+
+#[staged_builder]
+#[builder(const)]
+struct ConstBuilder {
+    a: u32,
+    b: u32,
+    #[builder(default = 3)]
+    c: u32,
+}
+
+const CONST_BUILT: ConstBuilder = ConstBuilder::builder().a(1).b(2).build();
+
+#[test]
+fn const_builder() {
+    assert_eq!((CONST_BUILT.a, CONST_BUILT.b, CONST_BUILT.c), (1, 2, 3));
+
+    const WITH_OPTIONAL: ConstBuilder = ConstBuilder::builder().a(1).b(2).c(4).build();
+    assert_eq!(WITH_OPTIONAL.c, 4);
+}
+
+#[staged_builder]
+#[builder(sealed, update, clone, debug, deref_view)]
+#[derive(Debug, PartialEq)]
+struct Sealed<T> {
+    a: T,
+    #[builder(default)]
+    b: u32,
+}
+
+#[test]
+fn sealed() {
+    let builder = Sealed::builder().a("x").b(1);
+    assert_eq!(builder.b, 1);
+    let actual = builder.clone().build();
+    assert_eq!(actual, Sealed { a: "x", b: 1 });
+
+    let actual = sealed::Builder::from(actual).b(2).build();
+    assert_eq!(actual, Sealed { a: "x", b: 2 });
+}
+
+#[staged_builder]
+#[builder(derive(Hash, PartialEq, std::fmt::Debug))]
+struct DerivedStages {
+    a: u32,
+    b: &'static str,
+    #[builder(default)]
+    c: bool,
+}
+
+#[test]
+fn derived_stages() {
+    fn assert_hash<T: std::hash::Hash>(_: &T) {}
+
+    let stage = DerivedStages::builder().a(1);
+    assert_hash(&stage);
+    assert_eq!(stage, DerivedStages::builder().a(1));
+    assert_ne!(stage, DerivedStages::builder().a(2));
+
+    let complete = stage.b("x").c(true);
+    assert_hash(&complete);
+    assert_eq!(
+        format!("{:?}", complete),
+        r#"Builder(Complete { a: 1, b: "x", c: true })"#
+    );
+
+    let actual = complete.build();
+    assert_eq!((actual.a, actual.b, actual.c), (1, "x", true));
+}
+
+#[staged_builder]
+#[builder(from_single)]
+#[derive(PartialEq, Debug)]
+struct FromSingle {
+    #[builder(into)]
+    value: String,
+    #[builder(skip, default = 1)]
+    version: u32,
+}
+
+#[test]
+fn from_single() {
+    let actual: FromSingle = "foo".into();
+    let expected = FromSingle {
+        value: "foo".to_string(),
+        version: 1,
+    };
+    assert_eq!(actual, expected);
+
+    assert_eq!(FromSingle::from(String::from("foo")), expected);
+}
+
+#[staged_builder]
+#[builder(stage_suffix = "Step")]
+struct StageSuffix {
+    a: u32,
+    #[builder(stage = Second)]
+    b: u32,
+}
+
+#[test]
+fn stage_suffix() {
+    let stage: stage_suffix::Builder<stage_suffix::AStep> = StageSuffix::builder();
+    let stage: stage_suffix::Builder<stage_suffix::Second> = stage.a(1);
+    let actual = stage.b(2).build();
+    assert_eq!((actual.a, actual.b), (1, 2));
+}
+
+#[staged_builder]
+#[builder(with)]
+struct With {
+    a: u32,
+    #[builder(default)]
+    b: u32,
+    #[builder(default, into)]
+    c: String,
+    #[builder(list(item(type = u32)))]
+    d: Vec<u32>,
+}
+
+#[test]
+fn with() {
+    let actual = With::builder()
+        .a(1)
+        .b(2)
+        .with(|s| {
+            s.a += 10;
+            s.b *= 2;
+            s.c = "c".to_string();
+            s.d.extend([1, 2]);
+        })
+        .push_d(3)
+        .build();
+    assert_eq!(actual.a, 11);
+    assert_eq!(actual.b, 4);
+    assert_eq!(actual.c, "c");
+    assert_eq!(actual.d, [1, 2, 3]);
+}
+
+#[staged_builder]
+#[builder(update, getters, field_names, debug, clone)]
+struct DeprecatedFields {
+    #[deprecated = "use b"]
+    a: u32,
+    b: u32,
+    #[deprecated]
+    #[builder(default)]
+    c: u32,
+    #[deprecated]
+    #[builder(list(item(type = u32)))]
+    d: Vec<u32>,
+}
+
+#[test]
+#[allow(deprecated)]
+fn deprecated_fields() {
+    let actual = DeprecatedFields::builder().a(1).b(2).c(3).push_d(4).build();
+    assert_eq!(
+        (actual.a, actual.b, actual.c, &*actual.d),
+        (1, 2, 3, &[4][..])
+    );
+}
+
+#[staged_builder]
+#[builder(normalize)]
+#[derive(PartialEq, Debug)]
+struct Normalized {
+    #[builder(list(item(type = i32)))]
+    values: Vec<i32>,
+}
+
+impl Normalize for Normalized {
+    type Error = &'static str;
+
+    fn normalize(mut self) -> Result<Self, Self::Error> {
+        if self.values.is_empty() {
+            return Err("empty");
+        }
+        self.values.sort();
+        Ok(self)
+    }
+}
+
+#[test]
+fn normalize() {
+    let actual = Normalized::builder().values([3, 1, 2]).build();
+    assert_eq!(
+        actual,
+        Ok(Normalized {
+            values: vec![1, 2, 3]
+        })
+    );
+
+    let actual = Normalized::builder().build();
+    assert_eq!(actual, Err("empty"));
+}
+
+#[staged_builder]
+#[builder(by_ref)]
+struct MapFields {
+    a: u32,
+    #[builder(default = 5)]
+    x: u32,
+    #[builder(default = lazy(Self::LAZY.to_string()), into)]
+    lazy: String,
+    #[builder(list(item(type = u32)))]
+    list: Vec<u32>,
+    #[builder(option(item(type = u32)))]
+    option: Option<u32>,
+}
+
+impl MapFields {
+    const LAZY: &'static str = "lazy";
+}
+
+#[test]
+fn map_fields() {
+    let actual = MapFields::builder()
+        .a(1)
+        .map_x(|v| v * 2)
+        .map_lazy(|s| s.to_uppercase())
+        .map_list(|mut l| {
+            l.push(1);
+            l
+        })
+        .map_option(|o| o.or(Some(3)))
+        .build();
+    assert_eq!(actual.a, 1);
+    assert_eq!(actual.x, 10);
+    assert_eq!(actual.lazy, "LAZY");
+    assert_eq!(actual.list, [1]);
+    assert_eq!(actual.option, Some(3));
+
+    let mut builder = MapFields::builder().a(1);
+    builder.x(3).push_list(2);
+    let actual = builder.map_x(|v| v * 2).map_list(|l| l.repeat(2)).build();
+    assert_eq!(actual.x, 6);
+    assert_eq!(actual.list, [2, 2]);
+}
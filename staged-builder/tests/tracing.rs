@@ -0,0 +1,80 @@
+#![cfg(feature = "tracing")]
+
+use staged_builder::staged_builder;
+use std::sync::{Arc, Mutex};
+use tracing::field::{Field, Visit};
+use tracing::span::{Attributes, Id, Record};
+use tracing::{Event, Metadata, Subscriber};
+
+struct MessageCollector(Arc<Mutex<Vec<String>>>);
+
+impl Subscriber for MessageCollector {
+    fn enabled(&self, _: &Metadata<'_>) -> bool {
+        true
+    }
+
+    fn new_span(&self, _: &Attributes<'_>) -> Id {
+        Id::from_u64(1)
+    }
+
+    fn record(&self, _: &Id, _: &Record<'_>) {}
+
+    fn record_follows_from(&self, _: &Id, _: &Id) {}
+
+    fn event(&self, event: &Event<'_>) {
+        struct MessageVisitor<'a>(&'a mut Vec<String>);
+
+        impl Visit for MessageVisitor<'_> {
+            fn record_debug(&mut self, field: &Field, value: &dyn std::fmt::Debug) {
+                if field.name() == "message" {
+                    self.0.push(format!("{value:?}"));
+                }
+            }
+        }
+
+        event.record(&mut MessageVisitor(&mut self.0.lock().unwrap()));
+    }
+
+    fn enter(&self, _: &Id) {}
+
+    fn exit(&self, _: &Id) {}
+}
+
+#[derive(PartialEq, Debug)]
+#[staged_builder]
+#[builder(trace_defaults)]
+struct TraceDefaults {
+    required: u32,
+    #[builder(default)]
+    optional: u32,
+    #[builder(default = 5)]
+    explicit: u32,
+    #[builder(list(item(type = u32)))]
+    list: Vec<u32>,
+}
+
+#[test]
+fn trace_defaults() {
+    let messages = Arc::new(Mutex::new(vec![]));
+    let collector = MessageCollector(messages.clone());
+
+    let actual = tracing::subscriber::with_default(collector, || {
+        TraceDefaults::builder()
+            .required(1)
+            .explicit(2)
+            .push_list(3)
+            .build()
+    });
+    let expected = TraceDefaults {
+        required: 1,
+        optional: 0,
+        explicit: 2,
+        list: vec![3],
+    };
+    assert_eq!(actual, expected);
+
+    assert_eq!(
+        *messages.lock().unwrap(),
+        ["`optional` field of `TraceDefaults` was not set; using its default value"],
+    );
+}
@@ -0,0 +1,6 @@
+// The expected compiler output depends on the toolchain, so the snapshots match the version pinned by the CI test job.
+#[test]
+fn ui() {
+    let t = trybuild::TestCases::new();
+    t.compile_fail("tests/ui/*.rs");
+}
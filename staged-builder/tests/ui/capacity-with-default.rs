@@ -0,0 +1,9 @@
+use staged_builder::staged_builder;
+
+#[staged_builder]
+struct Foo {
+    #[builder(default = vec![1], list(item(type = u32), capacity = 8))]
+    a: Vec<u32>,
+}
+
+fn main() {}
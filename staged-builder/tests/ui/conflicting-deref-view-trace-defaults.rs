@@ -0,0 +1,11 @@
+use staged_builder::staged_builder;
+
+#[staged_builder]
+#[builder(deref_view, trace_defaults)]
+struct Foo {
+    a: i32,
+    #[builder(default)]
+    b: i32,
+}
+
+fn main() {}
@@ -0,0 +1,9 @@
+use staged_builder::staged_builder;
+
+#[staged_builder]
+#[builder(new, unordered)]
+struct Foo {
+    a: i32,
+}
+
+fn main() {}
@@ -0,0 +1,15 @@
+use staged_builder::{staged_builder, Validate};
+
+#[staged_builder]
+#[builder(new, validate)]
+struct Id(u32);
+
+impl Validate for Id {
+    type Error = String;
+
+    fn validate(&self) -> Result<(), String> {
+        Ok(())
+    }
+}
+
+fn main() {}
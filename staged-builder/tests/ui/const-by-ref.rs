@@ -0,0 +1,10 @@
+use staged_builder::staged_builder;
+
+#[staged_builder]
+#[builder(const, by_ref)]
+struct Foo {
+    #[builder(default)]
+    a: u32,
+}
+
+fn main() {}
@@ -0,0 +1,9 @@
+use staged_builder::staged_builder;
+
+#[staged_builder]
+struct Foo {
+    #[builder(custom(type = String))]
+    a: String,
+}
+
+fn main() {}
@@ -0,0 +1,9 @@
+use staged_builder::staged_builder;
+
+#[staged_builder]
+struct Foo {
+    #[builder(list(item(custom(convert = ToString::to_string))))]
+    a: Vec<String>,
+}
+
+fn main() {}
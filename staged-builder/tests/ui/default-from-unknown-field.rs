@@ -0,0 +1,10 @@
+use staged_builder::staged_builder;
+
+#[staged_builder]
+struct Foo {
+    #[builder(default_from = missing)]
+    a: u32,
+    b: u32,
+}
+
+fn main() {}
@@ -0,0 +1,11 @@
+use staged_builder::staged_builder;
+
+#[staged_builder]
+#[builder(default_struct)]
+struct Foo {
+    #[builder(default)]
+    a: u32,
+    b: u32,
+}
+
+fn main() {}
@@ -0,0 +1,15 @@
+#![deny(deprecated)]
+
+use staged_builder::staged_builder;
+
+#[staged_builder]
+struct Foo {
+    a: u32,
+    #[deprecated = "use `a` instead"]
+    #[builder(default)]
+    b: u32,
+}
+
+fn main() {
+    Foo::builder().a(1).b(2).build();
+}
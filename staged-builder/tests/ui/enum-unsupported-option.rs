@@ -0,0 +1,9 @@
+use staged_builder::staged_builder;
+
+#[staged_builder]
+#[builder(update)]
+enum Foo {
+    Bar { a: u32 },
+}
+
+fn main() {}
@@ -0,0 +1,12 @@
+use staged_builder::staged_builder;
+
+#[staged_builder]
+#[builder(flatten)]
+struct Foo {
+    #[builder(default)]
+    a: u32,
+    #[builder(skip, default)]
+    b: u32,
+}
+
+fn main() {}
@@ -0,0 +1,12 @@
+use staged_builder::staged_builder;
+
+#[staged_builder]
+#[builder(from_single)]
+struct Foo {
+    #[builder(into)]
+    a: String,
+    #[builder(default)]
+    b: u32,
+}
+
+fn main() {}
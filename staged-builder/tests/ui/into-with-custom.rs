@@ -0,0 +1,9 @@
+use staged_builder::staged_builder;
+
+#[staged_builder]
+struct Foo {
+    #[builder(into, custom(type = u32, convert = From::from))]
+    a: u64,
+}
+
+fn main() {}
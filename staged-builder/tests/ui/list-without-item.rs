@@ -0,0 +1,9 @@
+use staged_builder::staged_builder;
+
+#[staged_builder]
+struct Foo {
+    #[builder(list())]
+    a: Vec<u32>,
+}
+
+fn main() {}
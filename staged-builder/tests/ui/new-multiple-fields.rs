@@ -0,0 +1,10 @@
+use staged_builder::staged_builder;
+
+#[staged_builder]
+#[builder(new)]
+struct Foo {
+    a: u32,
+    b: u32,
+}
+
+fn main() {}
@@ -0,0 +1,12 @@
+use staged_builder::staged_builder;
+
+#[staged_builder]
+struct Foo {
+    #[builder(stage = Coords)]
+    x: i32,
+    z: i32,
+    #[builder(stage = Coords)]
+    y: i32,
+}
+
+fn main() {}
@@ -0,0 +1,11 @@
+use staged_builder::staged_builder;
+
+#[staged_builder]
+#[builder(sealed)]
+struct Foo {
+    a: u32,
+}
+
+fn main() {
+    let _ = foo::AStage {};
+}
@@ -0,0 +1,9 @@
+use staged_builder::staged_builder;
+
+#[staged_builder]
+struct Foo {
+    #[builder(try_into)]
+    a: u8,
+}
+
+fn main() {}
@@ -0,0 +1,9 @@
+use staged_builder::staged_builder;
+
+#[staged_builder]
+union Foo {
+    a: u32,
+    b: f32,
+}
+
+fn main() {}
@@ -0,0 +1,9 @@
+use staged_builder::staged_builder;
+
+#[staged_builder]
+struct Foo {
+    #[builder(defualt)]
+    a: i32,
+}
+
+fn main() {}
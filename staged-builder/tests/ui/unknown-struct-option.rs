@@ -0,0 +1,9 @@
+use staged_builder::staged_builder;
+
+#[staged_builder]
+#[builder(valdiate)]
+struct Foo {
+    a: i32,
+}
+
+fn main() {}
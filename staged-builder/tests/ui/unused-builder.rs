@@ -0,0 +1,14 @@
+#![deny(unused_must_use)]
+
+use staged_builder::staged_builder;
+
+#[staged_builder]
+struct Foo {
+    #[builder(default)]
+    a: u32,
+}
+
+fn main() {
+    let builder = Foo::builder();
+    builder.a(1);
+}
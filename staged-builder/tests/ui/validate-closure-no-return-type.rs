@@ -0,0 +1,9 @@
+use staged_builder::staged_builder;
+
+#[staged_builder]
+#[builder(validate = |s: &Foo| if s.a > 1 { Err("too big") } else { Ok(()) })]
+struct Foo {
+    a: u32,
+}
+
+fn main() {}